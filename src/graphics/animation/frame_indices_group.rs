@@ -34,6 +34,14 @@ impl FrameIndicesGroup {
             .all(|i| self.into_iter().any(|e| e.is_inside(i)))
     }
 
+    /// Every frame index, in order, with ranges expanded.
+    pub fn frames(&self) -> impl Iterator<Item = u32> + '_ {
+        self.values.iter().flat_map(|indices| match indices {
+            FrameIndices::Value(index) => *index..=*index,
+            FrameIndices::Range { from, to } => *from..=*to,
+        })
+    }
+
     pub fn insert(&mut self, indices: FrameIndices) {
         let pos = {
             if self.values.is_empty() {
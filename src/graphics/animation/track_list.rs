@@ -18,6 +18,26 @@ impl TrackList {
         &self.entries
     }
 
+    /// Every labeled track, including nested ones, parents first.
+    /// Nested labels are combined with their parents' labels using a separator.
+    pub fn labeled_entries(&self, separator: &str) -> Vec<(String, &Track)> {
+        let mut entries = Vec::new();
+
+        for track in &self.entries {
+            match &track.label {
+                Some(label) => {
+                    entries.push((label.clone(), track));
+                    entries.extend(track.tracks.labeled_entries(separator).into_iter().map(
+                        |(inner_label, t)| (format!("{}{}{}", label, separator, inner_label), t),
+                    ));
+                }
+                None => entries.extend(track.tracks.labeled_entries(separator)),
+            }
+        }
+
+        entries
+    }
+
     pub fn is_empty(&self) -> bool {
         self.entries.is_empty()
     }
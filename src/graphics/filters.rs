@@ -0,0 +1,126 @@
+use image::RgbaImage;
+
+/// Multiplies every pixel color channel by it's alpha.
+pub fn premultiply_alpha(buffer: &mut RgbaImage) {
+    for pixel in buffer.pixels_mut() {
+        let alpha = pixel[3] as u32;
+
+        for channel in pixel.0.iter_mut().take(3) {
+            *channel = ((*channel as u32 * alpha + 127) / 255) as u8;
+        }
+    }
+}
+
+/// Fills color channels of every fully transparent pixel with it's nearby non transparent pixels colors.
+/// Alpha channel is kept untouched.
+///
+/// It spreads outwards from non transparent pixels, a ring at a time, until every pixel is filled.
+pub fn alpha_bleed(buffer: &mut RgbaImage) {
+    let (width, height) = buffer.dimensions();
+    let index = |x: u32, y: u32| (y * width + x) as usize;
+
+    let mut filled = buffer.pixels().map(|p| p[3] > 0).collect::<Vec<bool>>();
+    let mut queued = filled.clone();
+    let mut ring = Vec::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            if !filled[index(x, y)]
+                && neighbors(x, y, width, height).any(|(nx, ny)| filled[index(nx, ny)])
+            {
+                queued[index(x, y)] = true;
+                ring.push((x, y));
+            }
+        }
+    }
+
+    while !ring.is_empty() {
+        // colors are only taken from previous rings
+        let colors = ring
+            .iter()
+            .map(|(x, y)| {
+                let mut sum = [0u32; 3];
+                let mut count = 0u32;
+
+                for (nx, ny) in neighbors(*x, *y, width, height) {
+                    if filled[index(nx, ny)] {
+                        let neighbor = buffer.get_pixel(nx, ny);
+
+                        for (s, channel) in sum.iter_mut().zip(neighbor.0.iter()) {
+                            *s += *channel as u32;
+                        }
+
+                        count += 1;
+                    }
+                }
+
+                sum.map(|s| (s / count) as u8)
+            })
+            .collect::<Vec<[u8; 3]>>();
+
+        let mut next_ring = Vec::new();
+
+        for ((x, y), color) in ring.iter().zip(colors) {
+            let pixel = buffer.get_pixel_mut(*x, *y);
+            pixel.0[..3].copy_from_slice(&color);
+            filled[index(*x, *y)] = true;
+        }
+
+        for (x, y) in ring {
+            for (nx, ny) in neighbors(x, y, width, height) {
+                if !queued[index(nx, ny)] {
+                    queued[index(nx, ny)] = true;
+                    next_ring.push((nx, ny));
+                }
+            }
+        }
+
+        ring = next_ring;
+    }
+}
+
+/// Halves buffer dimensions using an alpha weighted 2x2 box filter.
+/// Odd dimensions repeats it's last row or column.
+pub fn downsample(buffer: &RgbaImage) -> RgbaImage {
+    let (width, height) = buffer.dimensions();
+
+    RgbaImage::from_fn((width / 2).max(1), (height / 2).max(1), |x, y| {
+        let mut color_sum = [0u32; 3];
+        let mut alpha_sum = 0u32;
+        let mut unweighted_sum = [0u32; 3];
+
+        for (sx, sy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            let pixel = buffer.get_pixel((x * 2 + sx).min(width - 1), (y * 2 + sy).min(height - 1));
+            let alpha = pixel[3] as u32;
+
+            for ((color, unweighted), channel) in color_sum
+                .iter_mut()
+                .zip(unweighted_sum.iter_mut())
+                .zip(pixel.0.iter())
+            {
+                *color += *channel as u32 * alpha;
+                *unweighted += *channel as u32;
+            }
+
+            alpha_sum += alpha;
+        }
+
+        // fully transparent blocks keeps their (possibly bled) colors
+        let color = if alpha_sum > 0 {
+            color_sum.map(|c| ((c + alpha_sum / 2) / alpha_sum) as u8)
+        } else {
+            unweighted_sum.map(|c| ((c + 2) / 4) as u8)
+        };
+
+        image::Rgba([color[0], color[1], color[2], ((alpha_sum + 2) / 4) as u8])
+    })
+}
+
+fn neighbors(x: u32, y: u32, width: u32, height: u32) -> impl Iterator<Item = (u32, u32)> {
+    (-1i64..=1)
+        .flat_map(|dy| (-1i64..=1).map(move |dx| (dx, dy)))
+        .filter(|(dx, dy)| *dx != 0 || *dy != 0)
+        .map(move |(dx, dy)| (x as i64 + dx, y as i64 + dy))
+        .filter(move |(nx, ny)| *nx >= 0 && *ny >= 0 && *nx < width as i64 && *ny < height as i64)
+        .map(|(nx, ny)| (nx as u32, ny as u32))
+}
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use crate::graphics::{animation::Animation, Image};
 
 pub enum Graphic {
@@ -17,3 +19,14 @@ impl From<Animation> for Graphic {
         Graphic::Animation(animation)
     }
 }
+
+impl Graphic {
+    /// Source file path which yields this, if any.
+    pub fn source_path(&self) -> Option<&Path> {
+        match self {
+            Graphic::Empty => None,
+            Graphic::Image(image) => Some(&image.source_path),
+            Graphic::Animation(animation) => Some(&animation.source_path),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::math::Rectangle;
+use std::collections::BTreeMap;
+
+use crate::math::{Point, Rectangle};
 use image::{GenericImageView, RgbaImage, SubImage};
 
 #[derive(Debug)]
@@ -14,6 +16,12 @@ pub struct GraphicSource {
     /// Clipping region
     /// Usually to clip empty space to better packing
     pub region: Rectangle<u32>,
+
+    /// Pivot point at source canvas, when source format provides one
+    pub pivot: Option<Point<i32>>,
+
+    /// Named regions at source canvas, which aren't part of graphic's image
+    pub hitboxes: BTreeMap<String, Rectangle<u32>>,
 }
 
 impl GraphicSource {
@@ -22,6 +30,8 @@ impl GraphicSource {
             atlas_region: None,
             buffer,
             region,
+            pivot: None,
+            hitboxes: BTreeMap::new(),
         }
     }
 
@@ -33,4 +43,15 @@ impl GraphicSource {
             self.region.height,
         )
     }
+
+    /// Clipping region upscaled by an integer factor, using nearest-neighbor.
+    pub fn scaled_region_buffer(&self, scale: u32) -> RgbaImage {
+        let view = self.region_buffer_view();
+
+        RgbaImage::from_fn(
+            self.region.width * scale,
+            self.region.height * scale,
+            |x, y| view.get_pixel(x / scale, y / scale),
+        )
+    }
 }
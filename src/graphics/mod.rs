@@ -1,8 +1,10 @@
 pub mod animation;
 mod error;
+pub mod filters;
 mod graphic;
 mod graphic_source;
 mod image;
+pub mod texture;
 
 pub use crate::graphics::image::Image;
 pub use error::Error;
@@ -0,0 +1,162 @@
+//! BC3 (also known as DXT5): BC4 alpha block followed by a BC1 color block.
+
+use super::block::{self, Block};
+
+pub fn encode_block(pixels: &Block) -> [u8; 16] {
+    let mut data = [0u8; 16];
+    data[..8].copy_from_slice(&encode_alpha(pixels));
+    data[8..].copy_from_slice(&encode_color(pixels));
+    data
+}
+
+fn encode_alpha(pixels: &Block) -> [u8; 8] {
+    let min = pixels.iter().map(|p| p[3]).min().unwrap();
+    let max = pixels.iter().map(|p| p[3]).max().unwrap();
+
+    if min == max {
+        return [max, min, 0, 0, 0, 0, 0, 0];
+    }
+
+    // max > min, so 8 interpolated values mode is used
+    let (a0, a1) = (max as i32, min as i32);
+    let mut palette = [a0, a1, 0, 0, 0, 0, 0, 0];
+
+    for (k, value) in palette.iter_mut().enumerate().skip(2) {
+        let k = k as i32;
+        *value = ((8 - k) * a0 + (k - 1) * a1 + 3) / 7;
+    }
+
+    let mut indices = 0u64;
+
+    for (i, pixel) in pixels.iter().enumerate() {
+        let index = block::nearest(&palette, |value| (value - pixel[3] as i32).abs());
+        indices |= (index as u64) << (i * 3);
+    }
+
+    let mut data = [0u8; 8];
+    data[0] = max;
+    data[1] = min;
+    data[2..].copy_from_slice(&indices.to_le_bytes()[..6]);
+    data
+}
+
+fn encode_color(pixels: &Block) -> [u8; 8] {
+    let points = pixels.map(|p| [p[0] as f32, p[1] as f32, p[2] as f32]);
+    let (start, end) = block::principal_endpoints(&points);
+
+    let (mut c0, mut c1) = (pack_565(&end), pack_565(&start));
+
+    if c0 < c1 {
+        std::mem::swap(&mut c0, &mut c1);
+    }
+
+    let mut data = [0u8; 8];
+    data[..2].copy_from_slice(&c0.to_le_bytes());
+    data[2..4].copy_from_slice(&c1.to_le_bytes());
+
+    if c0 == c1 {
+        // every index points to first color
+        return data;
+    }
+
+    let (e0, e1) = (unpack_565(c0), unpack_565(c1));
+    let palette = [
+        e0,
+        e1,
+        [0, 1, 2].map(|c| (2 * e0[c] + e1[c]) / 3),
+        [0, 1, 2].map(|c| (e0[c] + 2 * e1[c]) / 3),
+    ];
+
+    let mut indices = 0u32;
+
+    for (i, pixel) in pixels.iter().enumerate() {
+        let index = block::nearest(&palette, |color| block::distance::<3>(color, pixel));
+        indices |= (index as u32) << (i * 2);
+    }
+
+    data[4..].copy_from_slice(&indices.to_le_bytes());
+    data
+}
+
+fn pack_565(color: &[f32; 3]) -> u16 {
+    let r = (color[0] * 31.0 / 255.0).round() as u16;
+    let g = (color[1] * 63.0 / 255.0).round() as u16;
+    let b = (color[2] * 31.0 / 255.0).round() as u16;
+    (r << 11) | (g << 5) | b
+}
+
+fn unpack_565(color: u16) -> [i32; 3] {
+    let r = ((color >> 11) & 0x1F) as i32;
+    let g = ((color >> 5) & 0x3F) as i32;
+    let b = (color & 0x1F) as i32;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::texture::block::samples;
+
+    fn decode(data: &[u8; 16]) -> Block {
+        let (a0, a1) = (data[0] as u32, data[1] as u32);
+        let alphas: [u32; 8] = if a0 > a1 {
+            [0, 1, 2, 3, 4, 5, 6, 7].map(|k| match k {
+                0 => a0,
+                1 => a1,
+                k => ((8 - k) * a0 + (k - 1) * a1) / 7,
+            })
+        } else {
+            [0, 1, 2, 3, 4, 5, 6, 7].map(|k| match k {
+                0 => a0,
+                1 => a1,
+                6 => 0,
+                7 => 255,
+                k => ((6 - k) * a0 + (k - 1) * a1) / 5,
+            })
+        };
+
+        let mut alpha_indices = [0u8; 8];
+        alpha_indices[..6].copy_from_slice(&data[2..8]);
+        let alpha_indices = u64::from_le_bytes(alpha_indices);
+
+        let c0 = unpack_565(u16::from_le_bytes([data[8], data[9]]));
+        let c1 = unpack_565(u16::from_le_bytes([data[10], data[11]]));
+        let colors = [
+            c0,
+            c1,
+            [0, 1, 2].map(|c| (2 * c0[c] + c1[c]) / 3),
+            [0, 1, 2].map(|c| (c0[c] + 2 * c1[c]) / 3),
+        ];
+        let color_indices = u32::from_le_bytes([data[12], data[13], data[14], data[15]]);
+
+        let mut pixels = [[0u8; 4]; 16];
+
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            let color = colors[((color_indices >> (i * 2)) & 0b11) as usize];
+            let alpha = alphas[((alpha_indices >> (i * 3)) & 0b111) as usize];
+            *pixel = [color[0] as u8, color[1] as u8, color[2] as u8, alpha as u8];
+        }
+
+        pixels
+    }
+
+    #[test]
+    fn solid_block_is_preserved() {
+        let pixels = samples::solid([255, 0, 255, 128]);
+        let data = encode_block(&pixels);
+
+        assert_eq!(&data[..2], &[128, 128]);
+        assert_eq!(decode(&data), pixels);
+    }
+
+    #[test]
+    fn two_colors_block_is_preserved() {
+        let pixels = samples::two_colors([255, 0, 0, 255], [0, 0, 255, 0]);
+
+        assert_eq!(decode(&encode_block(&pixels)), pixels);
+    }
+}
@@ -0,0 +1,146 @@
+//! BC7, using only mode 6: a single RGBA subset with 7 bits end points,
+//! an unique p-bit to each end point and 4 bits indices.
+
+use super::block::{self, Block};
+
+const WEIGHTS: [i32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+pub fn encode_block(pixels: &Block) -> [u8; 16] {
+    let points = pixels.map(|p| p.map(|channel| channel as f32));
+    let (start, end) = block::principal_endpoints(&points);
+    let mut endpoints = [quantize(&start), quantize(&end)];
+
+    let palette = palette(&endpoints[0], &endpoints[1]);
+    let mut indices =
+        pixels.map(|pixel| block::nearest(&palette, |color| block::distance::<4>(color, &pixel)));
+
+    // first index most significant bit is implicit (always 0)
+    if indices[0] >= 8 {
+        endpoints.swap(0, 1);
+        indices = indices.map(|index| 15 - index);
+    }
+
+    let mut writer = BitWriter::new();
+
+    // mode 6
+    writer.write(1 << 6, 7);
+
+    for channel in 0..4 {
+        for (color, _) in &endpoints {
+            writer.write(color[channel] as u128, 7);
+        }
+    }
+
+    for (_, p_bit) in &endpoints {
+        writer.write(*p_bit as u128, 1);
+    }
+
+    for (i, index) in indices.iter().enumerate() {
+        writer.write(*index as u128, if i == 0 { 3 } else { 4 });
+    }
+
+    writer.value.to_le_bytes()
+}
+
+/// Quantizes to 7 bits channels, with a shared p-bit, choosing the p-bit with less error.
+fn quantize(color: &[f32; 4]) -> ([u8; 4], u8) {
+    (0..2u8)
+        .map(|p_bit| {
+            let quantized =
+                color.map(|c| ((c - p_bit as f32) / 2.0).round().clamp(0.0, 127.0) as u8);
+            let error = quantized
+                .iter()
+                .zip(color)
+                .map(|(q, c)| {
+                    let d = (*q as u32 * 2 + p_bit as u32) as f32 - c;
+                    d * d
+                })
+                .sum::<f32>();
+
+            ((quantized, p_bit), error)
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(endpoint, _)| endpoint)
+        .unwrap()
+}
+
+fn palette(e0: &([u8; 4], u8), e1: &([u8; 4], u8)) -> [[i32; 4]; 16] {
+    let expand = |(color, p_bit): &([u8; 4], u8)| color.map(|c| ((c << 1) | p_bit) as i32);
+    let (e0, e1) = (expand(e0), expand(e1));
+
+    WEIGHTS.map(|w| [0, 1, 2, 3].map(|c| ((64 - w) * e0[c] + w * e1[c] + 32) >> 6))
+}
+
+struct BitWriter {
+    value: u128,
+    position: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            value: 0,
+            position: 0,
+        }
+    }
+
+    fn write(&mut self, bits: u128, length: u32) {
+        self.value |= bits << self.position;
+        self.position += length;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::texture::block::samples;
+
+    fn decode(data: &[u8; 16]) -> Block {
+        let bits = u128::from_le_bytes(*data);
+        let mut position = 0;
+        let mut read = |length: u32| {
+            let value = (bits >> position) & ((1 << length) - 1);
+            position += length;
+            value as i32
+        };
+
+        assert_eq!(read(7), 1 << 6, "mode 6 is expected");
+
+        let mut endpoints = [[0i32; 4]; 2];
+
+        for channel in 0..4 {
+            for endpoint in endpoints.iter_mut() {
+                endpoint[channel] = read(7) << 1;
+            }
+        }
+
+        for endpoint in endpoints.iter_mut() {
+            let p_bit = read(1);
+            *endpoint = endpoint.map(|c| c | p_bit);
+        }
+
+        let mut pixels = [[0u8; 4]; 16];
+
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            let w = WEIGHTS[read(if i == 0 { 3 } else { 4 }) as usize];
+            *pixel = [0, 1, 2, 3]
+                .map(|c| (((64 - w) * endpoints[0][c] + w * endpoints[1][c] + 32) >> 6) as u8);
+        }
+
+        pixels
+    }
+
+    #[test]
+    fn solid_block_is_preserved() {
+        let pixels = samples::solid([200, 100, 50, 255]);
+
+        samples::assert_close(&decode(&encode_block(&pixels)), &pixels, 1);
+    }
+
+    #[test]
+    fn two_colors_block_is_preserved() {
+        let pixels = samples::two_colors([255, 0, 0, 255], [0, 0, 255, 0]);
+
+        samples::assert_close(&decode(&encode_block(&pixels)), &pixels, 1);
+    }
+}
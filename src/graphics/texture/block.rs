@@ -0,0 +1,154 @@
+use image::RgbaImage;
+
+/// 4x4 pixels, in row-major order.
+pub type Block = [[u8; 4]; 16];
+
+/// Blocks needed to cover provided dimensions.
+pub fn count(width: u32, height: u32) -> (u32, u32) {
+    (width.div_ceil(4), height.div_ceil(4))
+}
+
+pub fn extract(buffer: &RgbaImage, block_x: u32, block_y: u32) -> Block {
+    let (width, height) = buffer.dimensions();
+    let mut pixels = [[0u8; 4]; 16];
+
+    for (i, pixel) in pixels.iter_mut().enumerate() {
+        let x = (block_x * 4 + i as u32 % 4).min(width - 1);
+        let y = (block_y * 4 + i as u32 / 4).min(height - 1);
+        *pixel = buffer.get_pixel(x, y).0;
+    }
+
+    pixels
+}
+
+/// End points, clamped to [0, 255], of the line which best fits provided points.
+///
+/// Line follows points principal axis (found by power iteration over their covariance)
+/// and it's end points are the extreme points projections.
+pub fn principal_endpoints<const N: usize>(points: &[[f32; N]]) -> ([f32; N], [f32; N]) {
+    let count = points.len() as f32;
+    let mut mean = [0f32; N];
+
+    for point in points {
+        for (m, v) in mean.iter_mut().zip(point) {
+            *m += v / count;
+        }
+    }
+
+    let mut covariance = [[0f32; N]; N];
+
+    for point in points {
+        for (row, a) in covariance.iter_mut().zip(point.iter().zip(&mean)) {
+            for (c, b) in row.iter_mut().zip(point.iter().zip(&mean)) {
+                *c += (a.0 - a.1) * (b.0 - b.1);
+            }
+        }
+    }
+
+    // starting from the row of the widest channel keeps the power iteration away from
+    // a vector orthogonal to the principal axis (e.g. red and blue only blocks)
+    let widest = (0..N)
+        .max_by(|&a, &b| covariance[a][a].total_cmp(&covariance[b][b]))
+        .unwrap();
+
+    if covariance[widest][widest] <= f32::EPSILON {
+        // every point is the same
+        return (mean, mean);
+    }
+
+    let length = covariance[widest].iter().map(|v| v * v).sum::<f32>().sqrt();
+    let mut axis = covariance[widest].map(|v| v / length);
+
+    for _ in 0..8 {
+        let mut next = [0f32; N];
+
+        for (n, row) in next.iter_mut().zip(&covariance) {
+            *n = row.iter().zip(&axis).map(|(c, a)| c * a).sum();
+        }
+
+        let length = next.iter().map(|v| v * v).sum::<f32>().sqrt();
+
+        if length <= f32::EPSILON {
+            break;
+        }
+
+        axis = next.map(|v| v / length);
+    }
+
+    let (mut min, mut max) = (f32::MAX, f32::MIN);
+
+    for point in points {
+        let t = point
+            .iter()
+            .zip(&mean)
+            .zip(&axis)
+            .map(|((p, m), a)| (p - m) * a)
+            .sum::<f32>();
+
+        min = min.min(t);
+        max = max.max(t);
+    }
+
+    let mut start = [0f32; N];
+    let mut end = [0f32; N];
+
+    for (((s, e), m), a) in start.iter_mut().zip(end.iter_mut()).zip(&mean).zip(&axis) {
+        *s = (m + a * min).clamp(0.0, 255.0);
+        *e = (m + a * max).clamp(0.0, 255.0);
+    }
+
+    (start, end)
+}
+
+/// Squared distance between two colors, at their first N channels.
+pub fn distance<const N: usize>(a: &[i32], b: &[u8]) -> i32 {
+    a.iter()
+        .zip(b)
+        .take(N)
+        .map(|(a, b)| {
+            let d = a - *b as i32;
+            d * d
+        })
+        .sum()
+}
+
+/// Index of palette entry with smallest error.
+pub fn nearest<T, F: Fn(&T) -> i32>(palette: &[T], error: F) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, entry)| error(entry))
+        .map(|(index, _)| index)
+        .unwrap()
+}
+
+/// Blocks to verify encoders against their decoded result.
+#[cfg(test)]
+pub mod samples {
+    use super::Block;
+
+    pub fn solid(color: [u8; 4]) -> Block {
+        [color; 16]
+    }
+
+    /// Left two columns with a color and right two columns with another one.
+    pub fn two_colors(left: [u8; 4], right: [u8; 4]) -> Block {
+        let mut pixels = [left; 16];
+
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            if i % 4 >= 2 {
+                *pixel = right;
+            }
+        }
+
+        pixels
+    }
+
+    pub fn assert_close(decoded: &Block, expected: &Block, tolerance: u8) {
+        for (i, (d, e)) in decoded.iter().zip(expected).enumerate() {
+            let is_close = d.iter().zip(e).all(|(d, e)| d.abs_diff(*e) <= tolerance);
+
+            assert!(is_close, "pixel {} decoded as {:?}, expected {:?}", i, d, e);
+        }
+    }
+}
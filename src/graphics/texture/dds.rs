@@ -0,0 +1,210 @@
+//! DirectDraw Surface container.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::settings::TextureCompression;
+
+const MAGIC: &[u8; 4] = b"DDS ";
+const HEADER_SIZE: u32 = 124;
+const PIXEL_FORMAT_SIZE: u32 = 32;
+
+const DDSD_CAPS: u32 = 0x1;
+const DDSD_HEIGHT: u32 = 0x2;
+const DDSD_WIDTH: u32 = 0x4;
+const DDSD_PIXELFORMAT: u32 = 0x1000;
+const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+const DDSD_LINEARSIZE: u32 = 0x80000;
+
+const DDPF_FOURCC: u32 = 0x4;
+
+const DDSCAPS_COMPLEX: u32 = 0x8;
+const DDSCAPS_TEXTURE: u32 = 0x1000;
+const DDSCAPS_MIPMAP: u32 = 0x400000;
+
+const DXGI_FORMAT_BC7_UNORM: u32 = 98;
+const D3D10_RESOURCE_DIMENSION_TEXTURE2D: u32 = 3;
+const DDS_ALPHA_MODE_STRAIGHT: u32 = 1;
+const DDS_ALPHA_MODE_PREMULTIPLIED: u32 = 2;
+
+/// Checks if compression can be stored at a dds file.
+pub fn supports(compression: TextureCompression) -> bool {
+    match compression {
+        TextureCompression::Bc3 | TextureCompression::Bc7 => true,
+        TextureCompression::Etc2 => false,
+    }
+}
+
+/// Writes every encoded level, from largest to smallest, into a dds file.
+pub fn save<P: AsRef<Path>>(
+    path: P,
+    width: u32,
+    height: u32,
+    levels: &[Vec<u8>],
+    compression: TextureCompression,
+    premultiplied_alpha: bool,
+) -> io::Result<()> {
+    if !supports(compression) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} compression can't be stored at dds", compression),
+        ));
+    }
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    let has_mipmaps = levels.len() > 1;
+
+    let mut flags = DDSD_CAPS | DDSD_HEIGHT | DDSD_WIDTH | DDSD_PIXELFORMAT | DDSD_LINEARSIZE;
+    let mut caps = DDSCAPS_TEXTURE;
+
+    if has_mipmaps {
+        flags |= DDSD_MIPMAPCOUNT;
+        caps |= DDSCAPS_COMPLEX | DDSCAPS_MIPMAP;
+    }
+
+    let four_cc = match compression {
+        TextureCompression::Bc3 => b"DXT5",
+        _ => b"DX10",
+    };
+
+    writer.write_all(MAGIC)?;
+
+    let header = [
+        HEADER_SIZE,
+        flags,
+        height,
+        width,
+        levels[0].len() as u32, // linear size
+        0,                      // depth
+        levels.len() as u32,    // mipmap count
+    ];
+
+    for value in header {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+
+    // reserved
+    writer.write_all(&[0u8; 11 * 4])?;
+
+    // pixel format
+    writer.write_all(&PIXEL_FORMAT_SIZE.to_le_bytes())?;
+    writer.write_all(&DDPF_FOURCC.to_le_bytes())?;
+    writer.write_all(four_cc)?;
+
+    // rgb bit count and masks
+    writer.write_all(&[0u8; 5 * 4])?;
+
+    writer.write_all(&caps.to_le_bytes())?;
+
+    // caps 2, 3 and 4 and reserved
+    writer.write_all(&[0u8; 4 * 4])?;
+
+    if four_cc == b"DX10" {
+        let alpha_mode = if premultiplied_alpha {
+            DDS_ALPHA_MODE_PREMULTIPLIED
+        } else {
+            DDS_ALPHA_MODE_STRAIGHT
+        };
+
+        let header_dx10 = [
+            DXGI_FORMAT_BC7_UNORM,
+            D3D10_RESOURCE_DIMENSION_TEXTURE2D,
+            0, // misc flag
+            1, // array size
+            alpha_mode,
+        ];
+
+        for value in header_dx10 {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+    }
+
+    for level in levels {
+        writer.write_all(level)?;
+    }
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn saved(name: &str, levels: &[Vec<u8>], compression: TextureCompression) -> Vec<u8> {
+        let path = env::temp_dir().join(name);
+        save(&path, 8, 4, levels, compression, true).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn bc3_header() {
+        let levels = vec![vec![1u8; 32], vec![2u8; 16]];
+        let bytes = saved("clymene_dds_bc3.dds", &levels, TextureCompression::Bc3);
+
+        assert_eq!(&bytes[..4], b"DDS ");
+        assert_eq!(u32_at(&bytes, 4), 124);
+        assert_eq!(
+            u32_at(&bytes, 8),
+            0x1 | 0x2 | 0x4 | 0x1000 | 0x20000 | 0x80000
+        );
+        assert_eq!(u32_at(&bytes, 12), 4); // height
+        assert_eq!(u32_at(&bytes, 16), 8); // width
+        assert_eq!(u32_at(&bytes, 20), 32); // linear size
+        assert_eq!(u32_at(&bytes, 28), 2); // mipmap count
+        assert_eq!(u32_at(&bytes, 76), 32); // pixel format size
+        assert_eq!(u32_at(&bytes, 80), 0x4); // fourcc flag
+        assert_eq!(&bytes[84..88], b"DXT5");
+        assert_eq!(u32_at(&bytes, 108), 0x8 | 0x1000 | 0x400000);
+
+        // levels follows header
+        assert_eq!(bytes.len(), 128 + 48);
+        assert_eq!(&bytes[128..160], &levels[0][..]);
+        assert_eq!(&bytes[160..], &levels[1][..]);
+    }
+
+    #[test]
+    fn bc7_dx10_header() {
+        let levels = vec![vec![3u8; 32]];
+        let bytes = saved("clymene_dds_bc7.dds", &levels, TextureCompression::Bc7);
+
+        assert_eq!(u32_at(&bytes, 8), 0x1 | 0x2 | 0x4 | 0x1000 | 0x80000);
+        assert_eq!(u32_at(&bytes, 28), 1); // mipmap count
+        assert_eq!(&bytes[84..88], b"DX10");
+        assert_eq!(u32_at(&bytes, 108), 0x1000);
+
+        // dx10 header
+        assert_eq!(u32_at(&bytes, 128), 98); // DXGI_FORMAT_BC7_UNORM
+        assert_eq!(u32_at(&bytes, 132), 3); // texture 2d
+        assert_eq!(u32_at(&bytes, 136), 0); // misc flag
+        assert_eq!(u32_at(&bytes, 140), 1); // array size
+        assert_eq!(u32_at(&bytes, 144), 2); // premultiplied alpha
+
+        assert_eq!(&bytes[148..], &levels[0][..]);
+    }
+
+    #[test]
+    fn etc2_is_rejected() {
+        let path = env::temp_dir().join("clymene_dds_etc2.dds");
+
+        assert!(save(
+            &path,
+            4,
+            4,
+            &[vec![0u8; 16]],
+            TextureCompression::Etc2,
+            false
+        )
+        .is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,342 @@
+//! ETC2 RGBA8: EAC alpha block followed by an ETC2 color block.
+//!
+//! Color block only uses ETC1 compatible individual and differential modes.
+//! Both blocks are written in big-endian and index pixels in column-major order.
+
+use super::block::Block;
+
+const ALPHA_MODIFIERS: [[i32; 8]; 16] = [
+    [-3, -6, -9, -15, 2, 5, 8, 14],
+    [-3, -7, -10, -13, 2, 6, 9, 12],
+    [-2, -5, -8, -13, 1, 4, 7, 12],
+    [-2, -4, -6, -13, 1, 3, 5, 12],
+    [-3, -6, -8, -12, 2, 5, 7, 11],
+    [-3, -7, -9, -11, 2, 6, 8, 10],
+    [-4, -7, -8, -11, 3, 6, 7, 10],
+    [-3, -5, -8, -11, 2, 4, 7, 10],
+    [-2, -6, -8, -10, 1, 5, 7, 9],
+    [-2, -5, -8, -10, 1, 4, 7, 9],
+    [-2, -4, -8, -10, 1, 3, 7, 9],
+    [-2, -5, -7, -10, 1, 4, 6, 9],
+    [-3, -4, -7, -10, 2, 3, 6, 9],
+    [-1, -2, -3, -10, 0, 1, 2, 9],
+    [-4, -6, -8, -9, 3, 5, 7, 8],
+    [-3, -5, -7, -9, 2, 4, 6, 8],
+];
+
+/// Alpha modifiers table which has a zero modifier (at index 4).
+const ALPHA_EXACT_TABLE: usize = 13;
+
+/// Intensity modifiers, indexed by pixel index value.
+const COLOR_MODIFIERS: [[i32; 4]; 8] = [
+    [2, 8, -2, -8],
+    [5, 17, -5, -17],
+    [9, 29, -9, -29],
+    [13, 42, -13, -42],
+    [18, 60, -18, -60],
+    [24, 80, -24, -80],
+    [33, 106, -33, -106],
+    [47, 183, -47, -183],
+];
+
+pub fn encode_block(pixels: &Block) -> [u8; 16] {
+    // reorder to column-major
+    let pixels: Block = {
+        let mut column_major = [[0u8; 4]; 16];
+
+        for (i, pixel) in column_major.iter_mut().enumerate() {
+            *pixel = pixels[(i % 4) * 4 + i / 4];
+        }
+
+        column_major
+    };
+
+    let mut data = [0u8; 16];
+    data[..8].copy_from_slice(&encode_alpha(&pixels).to_be_bytes());
+    data[8..].copy_from_slice(&encode_color(&pixels).to_be_bytes());
+    data
+}
+
+fn encode_alpha(pixels: &Block) -> u64 {
+    let min = pixels.iter().map(|p| p[3] as i32).min().unwrap();
+    let max = pixels.iter().map(|p| p[3] as i32).max().unwrap();
+
+    let (base, multiplier, table) = if min == max {
+        (min, 1, ALPHA_EXACT_TABLE)
+    } else {
+        let mut best = (i32::MAX, (0, 1, 0));
+
+        for (table, modifiers) in ALPHA_MODIFIERS.iter().enumerate() {
+            let modifiers_range = modifiers[7] - modifiers[3];
+            let multiplier = ((max - min + modifiers_range - 1) / modifiers_range).clamp(1, 15);
+
+            for multiplier in (multiplier - 1).max(1)..=(multiplier + 1).min(15) {
+                let center = (min + max - (modifiers[3] + modifiers[7]) * multiplier) / 2;
+
+                for base in (center - 2).max(0)..=(center + 2).min(255) {
+                    let error = pixels
+                        .iter()
+                        .map(|p| {
+                            let (_, error) =
+                                nearest_alpha(p[3] as i32, base, multiplier, modifiers);
+                            error
+                        })
+                        .sum::<i32>();
+
+                    if error < best.0 {
+                        best = (error, (base, multiplier, table));
+                    }
+                }
+            }
+        }
+
+        best.1
+    };
+
+    let mut block = ((base as u64) << 56) | ((multiplier as u64) << 52) | ((table as u64) << 48);
+
+    for (i, pixel) in pixels.iter().enumerate() {
+        let (index, _) = nearest_alpha(pixel[3] as i32, base, multiplier, &ALPHA_MODIFIERS[table]);
+
+        block |= (index as u64) << (45 - i * 3);
+    }
+
+    block
+}
+
+fn nearest_alpha(alpha: i32, base: i32, multiplier: i32, modifiers: &[i32; 8]) -> (usize, i32) {
+    modifiers
+        .iter()
+        .enumerate()
+        .map(|(index, modifier)| {
+            let value = (base + modifier * multiplier).clamp(0, 255);
+            (index, (value - alpha) * (value - alpha))
+        })
+        .min_by_key(|(_, error)| *error)
+        .unwrap()
+}
+
+fn encode_color(pixels: &Block) -> u64 {
+    let mut best: Option<(i32, u64)> = None;
+
+    for flip in [false, true] {
+        let subblocks = subblocks(pixels, flip);
+        let averages = subblocks.map(|subblock| average(&subblock));
+
+        // individual mode, 4 bits to each base color
+        let individual = averages.map(|avg| avg.map(|c| (c * 15.0 / 255.0).round() as i32));
+
+        // differential mode, 5 bits base color and a 3 bits signed difference to second one
+        let differential = averages.map(|avg| avg.map(|c| (c * 31.0 / 255.0).round() as i32));
+        let is_differential_valid = (0..3).all(|c| {
+            let difference = differential[1][c] - differential[0][c];
+            (-4..=3).contains(&difference)
+        });
+
+        let mut candidates = vec![(false, individual)];
+
+        if is_differential_valid {
+            candidates.push((true, differential));
+        }
+
+        for (is_differential, colors) in candidates {
+            let bases = colors.map(|color| {
+                color.map(|c| {
+                    if is_differential {
+                        (c << 3) | (c >> 2)
+                    } else {
+                        (c << 4) | c
+                    }
+                })
+            });
+
+            let fits = [0, 1].map(|s| fit_subblock(&subblocks[s], &bases[s]));
+            let error = fits[0].1 + fits[1].1;
+
+            let is_better = match best {
+                Some((best_error, _)) => error < best_error,
+                None => true,
+            };
+
+            if is_better {
+                let mut block = pack_base_colors(&colors, is_differential);
+                block |= (fits[0].0 as u64) << 37;
+                block |= (fits[1].0 as u64) << 34;
+                block |= (is_differential as u64) << 33;
+                block |= (flip as u64) << 32;
+
+                for (s, fit) in fits.iter().enumerate() {
+                    for (position, index) in subblock_positions(s, flip).iter().zip(fit.2) {
+                        block |= ((index as u64 >> 1) & 1) << (16 + position);
+                        block |= (index as u64 & 1) << position;
+                    }
+                }
+
+                best = Some((error, block));
+            }
+        }
+    }
+
+    best.unwrap().1
+}
+
+fn pack_base_colors(colors: &[[i32; 3]; 2], is_differential: bool) -> u64 {
+    let mut block = 0u64;
+
+    for (c, shift) in [(0, 56), (1, 48), (2, 40)] {
+        block |= if is_differential {
+            let difference = (colors[1][c] - colors[0][c]) & 0b111;
+            ((colors[0][c] as u64) << (shift + 3)) | ((difference as u64) << shift)
+        } else {
+            ((colors[0][c] as u64) << (shift + 4)) | ((colors[1][c] as u64) << shift)
+        };
+    }
+
+    block
+}
+
+/// Pixel positions (column-major) of a subblock.
+/// Not flipped subblocks are 2x4 side by side, otherwise they're 4x2 stacked.
+fn subblock_positions(subblock: usize, flip: bool) -> [usize; 8] {
+    let mut positions = [0usize; 8];
+
+    for (i, position) in positions.iter_mut().enumerate() {
+        let (x, y) = if flip {
+            (i % 4, subblock * 2 + i / 4)
+        } else {
+            (subblock * 2 + i / 4, i % 4)
+        };
+
+        *position = x * 4 + y;
+    }
+
+    positions
+}
+
+fn subblocks(pixels: &Block, flip: bool) -> [[[u8; 4]; 8]; 2] {
+    [0, 1].map(|s| subblock_positions(s, flip).map(|position| pixels[position]))
+}
+
+fn average(pixels: &[[u8; 4]; 8]) -> [f32; 3] {
+    [0, 1, 2].map(|c| pixels.iter().map(|p| p[c] as f32).sum::<f32>() / 8.0)
+}
+
+/// Finds best modifiers table to a subblock base color.
+/// Returns table index, it's error and every pixel index.
+fn fit_subblock(pixels: &[[u8; 4]; 8], base: &[i32; 3]) -> (usize, i32, [usize; 8]) {
+    COLOR_MODIFIERS
+        .iter()
+        .enumerate()
+        .map(|(table, modifiers)| {
+            let mut error = 0;
+            let indices = pixels.map(|pixel| {
+                let (index, pixel_error) = modifiers
+                    .iter()
+                    .enumerate()
+                    .map(|(index, modifier)| {
+                        let pixel_error = (0..3)
+                            .map(|c| {
+                                let d = (base[c] + modifier).clamp(0, 255) - pixel[c] as i32;
+                                d * d
+                            })
+                            .sum::<i32>();
+
+                        (index, pixel_error)
+                    })
+                    .min_by_key(|(_, pixel_error)| *pixel_error)
+                    .unwrap();
+
+                error += pixel_error;
+                index
+            });
+
+            (table, error, indices)
+        })
+        .min_by_key(|(_, error, _)| *error)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::texture::block::samples;
+
+    /// Modifiers, from specification, by pixel index value: `+a`, `+b`, `-a` and `-b`.
+    const INTENSITY_TABLES: [[i32; 2]; 8] = [
+        [2, 8],
+        [5, 17],
+        [9, 29],
+        [13, 42],
+        [18, 60],
+        [24, 80],
+        [33, 106],
+        [47, 183],
+    ];
+
+    fn bits(value: u64, from: u32, length: u32) -> i32 {
+        ((value >> from) & ((1 << length) - 1)) as i32
+    }
+
+    fn decode(data: &[u8; 16]) -> Block {
+        let alpha = u64::from_be_bytes(data[..8].try_into().unwrap());
+        let color = u64::from_be_bytes(data[8..].try_into().unwrap());
+
+        let (base, multiplier, table) =
+            (bits(alpha, 56, 8), bits(alpha, 52, 4), bits(alpha, 48, 4));
+        let is_differential = bits(color, 33, 1) == 1;
+        let flip = bits(color, 32, 1) == 1;
+        let tables = [bits(color, 37, 3), bits(color, 34, 3)];
+
+        let bases = [0, 1].map(|s| {
+            [59, 51, 43].map(|shift| {
+                if is_differential {
+                    let base = bits(color, shift, 5);
+                    let difference = (bits(color, shift - 3, 3) << 29) >> 29;
+                    let c = base + s * difference;
+                    (c << 3) | (c >> 2)
+                } else {
+                    let c = bits(color, shift + 1 - 4 * s as u32, 4);
+                    (c << 4) | c
+                }
+            })
+        });
+
+        let mut pixels = [[0u8; 4]; 16];
+
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            let (x, y) = (i % 4, i / 4);
+            let position = (x * 4 + y) as u32;
+            let subblock = if flip { y / 2 } else { x / 2 };
+
+            let index = (bits(color, 16 + position, 1) << 1) | bits(color, position, 1);
+            let [a, b] = INTENSITY_TABLES[tables[subblock] as usize];
+            let modifier = [a, b, -a, -b][index as usize];
+
+            let alpha_index = bits(alpha, 45 - position * 3, 3) as usize;
+            let alpha_modifier = ALPHA_MODIFIERS[table as usize][alpha_index];
+
+            *pixel = [
+                (bases[subblock][0] + modifier).clamp(0, 255) as u8,
+                (bases[subblock][1] + modifier).clamp(0, 255) as u8,
+                (bases[subblock][2] + modifier).clamp(0, 255) as u8,
+                (base + alpha_modifier * multiplier).clamp(0, 255) as u8,
+            ];
+        }
+
+        pixels
+    }
+
+    #[test]
+    fn solid_block_is_preserved() {
+        let pixels = samples::solid([136, 68, 204, 128]);
+
+        samples::assert_close(&decode(&encode_block(&pixels)), &pixels, 2);
+    }
+
+    #[test]
+    fn two_colors_block_is_preserved() {
+        let pixels = samples::two_colors([255, 0, 0, 255], [0, 0, 255, 0]);
+
+        samples::assert_close(&decode(&encode_block(&pixels)), &pixels, 2);
+    }
+}
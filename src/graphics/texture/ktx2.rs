@@ -0,0 +1,230 @@
+//! Khronos Texture 2.0 container.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::settings::TextureCompression;
+
+use super::BLOCK_BYTES;
+
+const IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+/// Header and index sizes, before level index.
+const HEADER_SIZE: usize = 80;
+const LEVEL_INDEX_ENTRY_SIZE: usize = 24;
+
+const KHR_DF_MODEL_BC3: u32 = 130;
+const KHR_DF_MODEL_BC7: u32 = 134;
+const KHR_DF_MODEL_ETC2: u32 = 161;
+const KHR_DF_PRIMARIES_BT709: u32 = 1;
+const KHR_DF_TRANSFER_LINEAR: u32 = 1;
+const KHR_DF_FLAG_ALPHA_PREMULTIPLIED: u32 = 1;
+
+const KHR_DF_CHANNEL_COLOR: u32 = 0;
+const KHR_DF_CHANNEL_ETC2_COLOR: u32 = 2;
+const KHR_DF_CHANNEL_ALPHA: u32 = 15;
+
+/// Writes every encoded level, from largest to smallest, into a ktx2 file.
+pub fn save<P: AsRef<Path>>(
+    path: P,
+    width: u32,
+    height: u32,
+    levels: &[Vec<u8>],
+    compression: TextureCompression,
+    premultiplied_alpha: bool,
+) -> io::Result<()> {
+    let vk_format: u32 = match compression {
+        TextureCompression::Bc3 => 137,  // VK_FORMAT_BC3_UNORM_BLOCK
+        TextureCompression::Bc7 => 145,  // VK_FORMAT_BC7_UNORM_BLOCK
+        TextureCompression::Etc2 => 151, // VK_FORMAT_ETC2_R8G8B8A8_UNORM_BLOCK
+    };
+
+    let dfd = data_format_descriptor(compression, premultiplied_alpha);
+    let dfd_offset = HEADER_SIZE + LEVEL_INDEX_ENTRY_SIZE * levels.len();
+
+    // levels are stored from smallest to largest, each one aligned to block size
+    let mut level_offsets = vec![0usize; levels.len()];
+    let mut offset = align(dfd_offset + dfd.len(), BLOCK_BYTES);
+
+    for (level_offset, level) in level_offsets.iter_mut().zip(levels).rev() {
+        *level_offset = offset;
+        offset = align(offset + level.len(), BLOCK_BYTES);
+    }
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&IDENTIFIER)?;
+
+    let header = [
+        vk_format,
+        1, // type size
+        width,
+        height,
+        0, // depth
+        0, // layer count
+        1, // face count
+        levels.len() as u32,
+        0, // supercompression scheme
+        dfd_offset as u32,
+        dfd.len() as u32,
+        0, // key/value data offset
+        0, // key/value data length
+    ];
+
+    for value in header {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+
+    // supercompression global data offset and length
+    writer.write_all(&[0u8; 2 * 8])?;
+
+    for (level_offset, level) in level_offsets.iter().zip(levels) {
+        writer.write_all(&(*level_offset as u64).to_le_bytes())?;
+        writer.write_all(&(level.len() as u64).to_le_bytes())?;
+        writer.write_all(&(level.len() as u64).to_le_bytes())?; // uncompressed length
+    }
+
+    writer.write_all(&dfd)?;
+    let mut position = dfd_offset + dfd.len();
+
+    for (level_offset, level) in level_offsets.iter().zip(levels).rev() {
+        writer.write_all(&vec![0u8; level_offset - position])?;
+        writer.write_all(level)?;
+        position = level_offset + level.len();
+    }
+
+    writer.flush()
+}
+
+/// Basic data format descriptor, preceded by it's total size.
+fn data_format_descriptor(compression: TextureCompression, premultiplied_alpha: bool) -> Vec<u8> {
+    // (channel, bit offset, bit length)
+    let (model, samples): (u32, &[(u32, u32, u32)]) = match compression {
+        TextureCompression::Bc3 => (
+            KHR_DF_MODEL_BC3,
+            &[
+                (KHR_DF_CHANNEL_ALPHA, 0, 64),
+                (KHR_DF_CHANNEL_COLOR, 64, 64),
+            ],
+        ),
+        TextureCompression::Bc7 => (KHR_DF_MODEL_BC7, &[(KHR_DF_CHANNEL_COLOR, 0, 128)]),
+        TextureCompression::Etc2 => (
+            KHR_DF_MODEL_ETC2,
+            &[
+                (KHR_DF_CHANNEL_ALPHA, 0, 64),
+                (KHR_DF_CHANNEL_ETC2_COLOR, 64, 64),
+            ],
+        ),
+    };
+
+    let flags = if premultiplied_alpha {
+        KHR_DF_FLAG_ALPHA_PREMULTIPLIED
+    } else {
+        0
+    };
+
+    let block_size = 24 + 16 * samples.len() as u32;
+
+    let mut words = vec![
+        4 + block_size,
+        0,                      // vendor id and descriptor type
+        2 | (block_size << 16), // version and descriptor block size
+        model | (KHR_DF_PRIMARIES_BT709 << 8) | (KHR_DF_TRANSFER_LINEAR << 16) | (flags << 24),
+        3 | (3 << 8),       // 4x4 texel block
+        BLOCK_BYTES as u32, // bytes plane 0
+        0,                  // bytes plane 4 to 7
+    ];
+
+    for (channel, bit_offset, bit_length) in samples {
+        words.push(bit_offset | ((bit_length - 1) << 16) | (channel << 24));
+        words.push(0); // sample position
+        words.push(0); // lower
+        words.push(u32::MAX); // upper
+    }
+
+    words.iter().flat_map(|word| word.to_le_bytes()).collect()
+}
+
+fn align(value: usize, alignment: usize) -> usize {
+    value.div_ceil(alignment) * alignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn u64_at(bytes: &[u8], offset: usize) -> usize {
+        u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap()) as usize
+    }
+
+    #[test]
+    fn header_level_index_and_data_format_descriptor() {
+        let levels = vec![vec![1u8; 32], vec![2u8; 16]];
+        let path = env::temp_dir().join("clymene_ktx2_etc2.ktx2");
+        save(&path, 8, 4, &levels, TextureCompression::Etc2, false).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(&bytes[..12], &IDENTIFIER);
+        assert_eq!(u32_at(&bytes, 12), 151); // VK_FORMAT_ETC2_R8G8B8A8_UNORM_BLOCK
+        assert_eq!(u32_at(&bytes, 16), 1); // type size
+        assert_eq!(u32_at(&bytes, 20), 8); // width
+        assert_eq!(u32_at(&bytes, 24), 4); // height
+        assert_eq!(u32_at(&bytes, 28), 0); // depth
+        assert_eq!(u32_at(&bytes, 32), 0); // layer count
+        assert_eq!(u32_at(&bytes, 36), 1); // face count
+        assert_eq!(u32_at(&bytes, 40), 2); // level count
+        assert_eq!(u32_at(&bytes, 44), 0); // supercompression
+
+        // level index, largest level first and every level aligned to block size
+        for (i, level) in levels.iter().enumerate() {
+            let entry = 80 + i * 24;
+            let (offset, length) = (u64_at(&bytes, entry), u64_at(&bytes, entry + 8));
+
+            assert_eq!(offset % 16, 0);
+            assert_eq!(length, level.len());
+            assert_eq!(u64_at(&bytes, entry + 16), level.len());
+            assert_eq!(&bytes[offset..offset + length], &level[..]);
+        }
+
+        // smallest level is stored first
+        assert!(u64_at(&bytes, 80 + 24) < u64_at(&bytes, 80));
+
+        // data format descriptor
+        let (dfd_offset, dfd_length) = (u32_at(&bytes, 48) as usize, u32_at(&bytes, 52));
+        assert_eq!(dfd_offset, 80 + 2 * 24);
+        assert_eq!(u32_at(&bytes, dfd_offset), dfd_length);
+
+        let block = dfd_offset + 4;
+        assert_eq!(u32_at(&bytes, block), 0); // vendor and descriptor type
+        assert_eq!(u32_at(&bytes, block + 4), 2 | ((24 + 2 * 16) << 16));
+        assert_eq!(u32_at(&bytes, block + 8) & 0xFF, 161); // etc2 model
+        assert_eq!(u32_at(&bytes, block + 12), 3 | (3 << 8)); // 4x4 texel block
+        assert_eq!(u32_at(&bytes, block + 16), 16); // bytes plane 0
+
+        // alpha sample then color sample
+        assert_eq!(u32_at(&bytes, block + 24), (63 << 16) | (15 << 24));
+        assert_eq!(u32_at(&bytes, block + 40), 64 | (63 << 16) | (2 << 24));
+        assert_eq!(dfd_length, 4 + 24 + 2 * 16);
+    }
+
+    #[test]
+    fn premultiplied_alpha_flag() {
+        let flags = |premultiplied_alpha| {
+            let dfd = data_format_descriptor(TextureCompression::Bc7, premultiplied_alpha);
+            u32_at(&dfd, 12) >> 24
+        };
+
+        assert_eq!(flags(false), 0);
+        assert_eq!(flags(true), 1);
+    }
+}
@@ -0,0 +1,38 @@
+//! Software encoders to gpu block compressed formats and their file containers.
+
+mod bc3;
+mod bc7;
+mod block;
+pub mod dds;
+mod etc2;
+pub mod ktx2;
+
+use image::RgbaImage;
+
+use crate::settings::TextureCompression;
+
+/// Bytes used by every 4x4 block, at any supported compression.
+pub const BLOCK_BYTES: usize = 16;
+
+/// Encodes a buffer into blocks, sequentially from top-left to bottom-right.
+///
+/// Dimensions which isn't a multiple of 4 are padded by repeating last row or column.
+pub fn encode(buffer: &RgbaImage, compression: TextureCompression) -> Vec<u8> {
+    let encode_block = match compression {
+        TextureCompression::Bc3 => bc3::encode_block,
+        TextureCompression::Bc7 => bc7::encode_block,
+        TextureCompression::Etc2 => etc2::encode_block,
+    };
+
+    let (blocks_x, blocks_y) = block::count(buffer.width(), buffer.height());
+    let mut data = Vec::with_capacity(blocks_x as usize * blocks_y as usize * BLOCK_BYTES);
+
+    for block_y in 0..blocks_y {
+        for block_x in 0..blocks_x {
+            let pixels = block::extract(buffer, block_x, block_y);
+            data.extend_from_slice(&encode_block(&pixels));
+        }
+    }
+
+    data
+}
@@ -1,9 +1,11 @@
 use num_traits::{Num, NumCast};
 use std::ops::{BitAnd, BitOrAssign, Shr};
 
+mod point;
 mod rectangle;
 mod size;
 
+pub use point::Point;
 pub use rectangle::Rectangle;
 pub use size::Size;
 
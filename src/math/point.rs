@@ -0,0 +1,27 @@
+use std::{cmp::PartialOrd, fmt};
+
+use num_traits::{cast::NumCast, Num};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Point<T: Num + NumCast + PartialOrd + Copy> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Num + NumCast + PartialOrd + Copy> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn scaled(&self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Num + NumCast + PartialOrd + Copy + fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, {}", self.x, self.y)
+    }
+}
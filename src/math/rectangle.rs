@@ -124,6 +124,15 @@ impl<T: Num + NumCast + PartialOrd + Copy> Rectangle<T> {
     pub fn area(&self) -> T {
         self.width * self.height
     }
+
+    pub fn scaled(&self, factor: T) -> Self {
+        Self::new(
+            self.x * factor,
+            self.y * factor,
+            self.width * factor,
+            self.height * factor,
+        )
+    }
 }
 
 impl<T: Unsigned + NumCast + PartialOrd + Copy> Rectangle<T> {
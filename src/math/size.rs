@@ -7,9 +7,11 @@ use num_traits::{
     Num,
 };
 
+use serde::{Deserialize, Serialize};
+
 use super::Rectangle;
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
 pub struct Size<T: Unsigned + NumCast + PartialOrd + Copy> {
     pub width: T,
     pub height: T,
@@ -41,6 +43,10 @@ impl<T: Unsigned + NumCast + PartialOrd + Copy> Size<T> {
     pub fn area(&self) -> T {
         self.width * self.height
     }
+
+    pub fn scaled(&self, factor: T) -> Self {
+        Self::new(self.width * factor, self.height * factor)
+    }
 }
 
 impl<T: Unsigned + NumCast + PartialOrd + Copy> From<Size<T>> for Rectangle<T> {
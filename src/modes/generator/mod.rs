@@ -13,7 +13,14 @@ use colored::Colorize;
 use processors::{
     cache::{CacheExporterProcessor, CacheImporterProcessor},
     config::ConfigProcessor,
-    data::DataProcessor,
+    data::{
+        exporters::{
+            BinaryExporter, CHeaderExporter, ClymeneJsonExporter, CocosPlistExporter, CssExporter,
+            GodotExporter, HtmlPreviewExporter, LibGdxExporter, MessagePackExporter, RustExporter,
+            SparrowExporter, TexturePackerExporter, TexturePackerLayout,
+        },
+        DataProcessor,
+    },
     image::{format_handlers::aseprite_handler, ImageProcessor},
     output::OutputProcessor,
     packer::{self, PackerProcessor},
@@ -86,6 +93,22 @@ impl Mode for GeneratorMode {
             aseprite_handler::AsepriteProcessor::RawFile,
         ));
 
+        let mut data_processor = DataProcessor::new();
+        data_processor
+            .register_exporter(ClymeneJsonExporter)
+            .register_exporter(MessagePackExporter)
+            .register_exporter(BinaryExporter)
+            .register_exporter(TexturePackerExporter::new(TexturePackerLayout::Hash))
+            .register_exporter(TexturePackerExporter::new(TexturePackerLayout::Array))
+            .register_exporter(LibGdxExporter)
+            .register_exporter(GodotExporter)
+            .register_exporter(SparrowExporter)
+            .register_exporter(CocosPlistExporter)
+            .register_exporter(RustExporter)
+            .register_exporter(CHeaderExporter)
+            .register_exporter(CssExporter)
+            .register_exporter(HtmlPreviewExporter);
+
         ProcessorsPipeline::new()
             // ensure essential config are working and prepare it to be at valid state
             .enqueue(ConfigProcessor::new())
@@ -98,7 +121,7 @@ impl Mode for GeneratorMode {
             // exports cache entries into file format again (to be reusable in next usage)
             .enqueue(CacheExporterProcessor::new())
             // get every data from previous steps and packs it together into a nicer format
-            .enqueue(DataProcessor::new())
+            .enqueue(data_processor)
             // copies registered output files from cache to user output dir path
             .enqueue(OutputProcessor::default())
             .start(config, args);
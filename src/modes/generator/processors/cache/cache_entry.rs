@@ -1,5 +1,5 @@
 use std::{
-    fs,
+    fs, io,
     path::{Path, PathBuf},
     time::SystemTime,
 };
@@ -15,6 +15,8 @@ use crate::{
         data::{FrameData, GraphicData},
         image::{GraphicSourceData, GraphicSourceDataSet},
     },
+    settings::CacheValidation,
+    util,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -22,6 +24,14 @@ pub struct CacheEntry {
     /// Last source file modified time>
     pub modtime: SystemTime,
 
+    /// Source file contents hash, only registered when validating by content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<u64>,
+
+    /// Hash of settings which source file was processed with.
+    #[serde(default)]
+    pub config_hash: u64,
+
     /// Source file extension
     pub extension: String,
 
@@ -40,12 +50,16 @@ pub struct CacheEntry {
 impl CacheEntry {
     pub fn new(
         modtime: SystemTime,
+        content_hash: Option<u64>,
+        config_hash: u64,
         extension: String,
         data: GraphicData,
         location: PathBuf,
     ) -> Self {
         Self {
             modtime,
+            content_hash,
+            config_hash,
             extension,
             data,
             location,
@@ -122,6 +136,26 @@ impl CacheEntry {
         Some(animation.into())
     }
 
+    /// Verifies if source file, at it's current state, is the one registered.
+    pub fn is_up_to_date(
+        &self,
+        source_path: &Path,
+        validation: CacheValidation,
+        config_hash: u64,
+    ) -> io::Result<bool> {
+        if self.config_hash != config_hash {
+            return Ok(false);
+        }
+
+        match validation {
+            CacheValidation::Modtime => Ok(source_path.metadata()?.modified()? == self.modtime),
+            CacheValidation::Content => match self.content_hash {
+                Some(content_hash) => Ok(util::hash::file_fnv1a_64(source_path)? == content_hash),
+                None => Ok(false),
+            },
+        }
+    }
+
     pub fn mark_as_invalid(&mut self) {
         self.invalid = true;
     }
@@ -130,3 +164,42 @@ impl CacheEntry {
         self.invalid
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn content_validation_ignores_modtime() {
+        let source_path = env::temp_dir().join("clymene_cache_entry_content.ase");
+        fs::write(&source_path, b"contents").unwrap();
+
+        let content_hash = util::hash::file_fnv1a_64(&source_path).unwrap();
+        let entry = CacheEntry::new(
+            SystemTime::UNIX_EPOCH,
+            Some(content_hash),
+            7,
+            "ase".to_owned(),
+            GraphicData::new(),
+            PathBuf::from("hero"),
+        );
+
+        assert!(entry
+            .is_up_to_date(&source_path, CacheValidation::Content, 7)
+            .unwrap());
+        assert!(!entry
+            .is_up_to_date(&source_path, CacheValidation::Modtime, 7)
+            .unwrap());
+        assert!(!entry
+            .is_up_to_date(&source_path, CacheValidation::Content, 8)
+            .unwrap());
+
+        fs::write(&source_path, b"changed contents").unwrap();
+        assert!(!entry
+            .is_up_to_date(&source_path, CacheValidation::Content, 7)
+            .unwrap());
+
+        fs::remove_file(&source_path).unwrap();
+    }
+}
@@ -8,9 +8,9 @@ use colored::Colorize;
 use crate::{
     common::Verbosity,
     graphics::{animation::Frame, Graphic},
-    math::Rectangle,
+    math::{Rectangle, Size},
     modes::generator::processors::{
-        data::{FrameData, GraphicData},
+        data::{FrameData, GraphicData, PivotData},
         ConfigStatus, Processor, State,
     },
     settings::{Config, ProcessorConfig},
@@ -61,13 +61,15 @@ impl CacheExporterProcessor {
         } else {
             infoln!("Initializing cache");
 
-            state.cache = Some(Cache::new(
+            let mut cache = Cache::new(
                 current_metadata,
                 c.cache.images_path(),
                 c.cache.atlas_path(),
-            ));
+            );
+            cache.validation = c.cache.validation;
+            cache.config_hash = c.image.processing_hash();
 
-            state.cache.as_mut().unwrap()
+            state.cache.insert(cache)
         };
 
         // insert graphics to cache (if isn't already registered)
@@ -78,7 +80,6 @@ impl CacheExporterProcessor {
         for g in state.graphic_output.graphics.iter() {
             let source_path;
             let location;
-            let source_metadata;
             let mut data = GraphicData::new();
 
             let graphic_cache_dir_path = match g {
@@ -89,9 +90,12 @@ impl CacheExporterProcessor {
                         .unwrap()
                         .with_extension("");
 
-                    source_metadata = image.source_path.metadata().unwrap();
-
                     // extract data
+                    data.canvas = Some(Size::new(
+                        image.graphic_source.buffer.width(),
+                        image.graphic_source.buffer.height(),
+                    ));
+
                     data.frames.push(FrameData::Contents {
                         atlas_region: match &image.graphic_source.atlas_region {
                             Some(atlas_region) => atlas_region.clone(),
@@ -102,6 +106,12 @@ impl CacheExporterProcessor {
                         },
                         duration: None,
                         source_region: image.graphic_source.region.clone(),
+                        pivot: image
+                            .graphic_source
+                            .pivot
+                            .clone()
+                            .map(|p| PivotData::new(p, &image.graphic_source.region)),
+                        hitboxes: image.graphic_source.hitboxes.clone(),
                     });
 
                     cache_images_path.join(&location)
@@ -113,13 +123,19 @@ impl CacheExporterProcessor {
                         .unwrap()
                         .with_extension("");
 
-                    source_metadata = source_path.metadata().unwrap();
-
                     // extract data
                     for track in animation.tracks.entries() {
                         data.tracks.register(track.clone());
                     }
 
+                    data.canvas = animation.frames.iter().find_map(|frame| match frame {
+                        Frame::Contents { graphic_source, .. } => Some(Size::new(
+                            graphic_source.buffer.width(),
+                            graphic_source.buffer.height(),
+                        )),
+                        Frame::Empty => None,
+                    });
+
                     for (index, frame) in animation.frames.iter().enumerate() {
                         data.frames.push(
                             match frame {
@@ -136,7 +152,9 @@ impl CacheExporterProcessor {
                                         },
                                     },
                                     duration: Some(*duration),
-                                    source_region: graphic_source.region.clone()
+                                    source_region: graphic_source.region.clone(),
+                                    pivot: graphic_source.pivot.clone().map(|p| PivotData::new(p, &graphic_source.region)),
+                                    hitboxes: graphic_source.hitboxes.clone(),
                                 }
                             }
                         );
@@ -159,9 +177,7 @@ impl CacheExporterProcessor {
                             .unwrap_or_default()
                             .to_owned();
 
-                        cache
-                            .register(location, ext, &source_metadata, data)
-                            .unwrap();
+                        cache.register(location, ext, source_path, data).unwrap();
                     }
                 }
                 Err(e) => {
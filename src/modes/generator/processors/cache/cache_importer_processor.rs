@@ -17,7 +17,7 @@ use crate::{
     util::{self, Timer},
 };
 
-use super::Cache;
+use super::{Cache, MigrationContext};
 
 pub struct CacheImporterProcessor {
     verbose: bool,
@@ -57,6 +57,8 @@ impl CacheImporterProcessor {
 
         traceln!("Initializing cache file");
         let mut cache = Cache::new(metadata, c.cache.images_path(), c.cache.atlas_path());
+        cache.validation = c.cache.validation;
+        cache.config_hash = c.image.processing_hash();
 
         // always start outdated
         cache.mark_as_outdated();
@@ -73,9 +75,11 @@ impl CacheImporterProcessor {
         cache
     }
 
-    fn handle_cache(&self, state: &State, cache: &mut Cache) {
+    fn handle_cache(&self, state: &State, cache: &mut Cache, current_metadata: &CacheMetadata) {
         let c = state.config.try_read().expect("Can't retrieve a read lock");
         let cache_dir_pathbuf = c.cache.entry_path();
+        cache.validation = c.cache.validation;
+        cache.config_hash = c.image.processing_hash();
 
         // atlas subdir
         self.ensure_exists_subdir(&cache_dir_pathbuf, "atlas")
@@ -87,6 +91,7 @@ impl CacheImporterProcessor {
 
         infoln!(block, "Verifying");
         self.verify_cache_status(&c.image.input_path, cache);
+        self.verify_stages(cache, current_metadata);
         doneln!();
 
         // remove invalid cache entries
@@ -295,10 +300,12 @@ impl CacheImporterProcessor {
                         // source file doesn't exists anymore
                         traceln!("[{}]: Source isn't a file", location.display());
                         is_invalid_entry = true;
-                    }
-
-                    if metadata.modified().unwrap() != entry_ref.borrow().modtime {
-                        // source file modtime doesn't matches cache entry data
+                    } else if !entry_ref
+                        .borrow()
+                        .is_up_to_date(&pathbuf, cache.validation, cache.config_hash)
+                        .unwrap()
+                    {
+                        // source file (or settings it's processed with) doesn't matches cache entry data
                         traceln!("[{}]: Source was modified", location.display());
                         is_modified_entry = true;
                     }
@@ -393,6 +400,23 @@ impl CacheImporterProcessor {
         }
     }
 
+    /// Marks every processor which config fingerprint differs from cached one as outdated.
+    fn verify_stages(&self, cache: &mut Cache, current_metadata: &CacheMetadata) {
+        let current_fingerprints = &current_metadata.generation_metadata().fingerprints;
+        let outdated_stages: Vec<&String> = current_fingerprints
+            .iter()
+            .filter(|(stage, fingerprint)| {
+                cache.meta.generation_metadata().fingerprints.get(*stage) != Some(fingerprint)
+            })
+            .map(|(stage, _)| stage)
+            .collect();
+
+        for stage in outdated_stages {
+            traceln!("[{}]: Settings were modified", stage);
+            cache.mark_stage_as_outdated(stage);
+        }
+    }
+
     fn ensure_exists_subdir(&self, cache_pathbuf: &Path, dir_name: &str) -> eyre::Result<PathBuf> {
         let pathbuf = cache_pathbuf.join(dir_name);
 
@@ -419,7 +443,8 @@ impl CacheImporterProcessor {
     }
 
     fn should_keep_cache(&self, previous: &CacheMetadata, current: &CacheMetadata) -> bool {
-        if previous.version() != current.version() {
+        // older schemas were already migrated when loaded
+        if previous.schema_version() != current.schema_version() {
             return false;
         }
 
@@ -549,24 +574,57 @@ impl Processor for CacheImporterProcessor {
         } else {
             let images_path = c.cache.images_path();
             let atlas_output_path = c.cache.atlas_path();
+            let migration_context = MigrationContext {
+                config_hash: c.image.processing_hash(),
+            };
 
-            match Cache::load_from_path(&cache_file_pathbuf, images_path, atlas_output_path) {
+            match Cache::load_from_path(
+                &cache_file_pathbuf,
+                images_path,
+                atlas_output_path,
+                &migration_context,
+            ) {
                 Ok(mut c) => {
+                    if let Some(schema_version) = c.migrated_from() {
+                        infoln!(
+                            "Migrated from schema version {} to {}",
+                            schema_version.to_string().bold(),
+                            CacheMetadata::SCHEMA_VERSION.to_string().bold()
+                        );
+
+                        // keep it upgraded, even if current execution doesn't finishes
+                        if let Err(e) = c.save_to_path(&cache_file_pathbuf) {
+                            warnln!("Can't save migrated cache file: {}", e);
+                        }
+                    }
+
                     if self.should_keep_cache(&c.meta, &current_metadata) {
                         // metadata matched expected values
 
-                        infoln!("Version {} matches", c.meta.version().bold());
-                        self.handle_cache(state, &mut c);
+                        infoln!(
+                            "Schema version {} matches",
+                            c.meta.schema_version().to_string().bold()
+                        );
+
+                        if c.meta.version() != current_metadata.version() {
+                            traceln!(
+                                entry: decorator::Entry::None,
+                                "Generated by version {}",
+                                c.meta.version().bold()
+                            );
+                        }
+
+                        self.handle_cache(state, &mut c, &current_metadata);
                         state_cache = c;
                     } else {
                         // metadata mismatch expected values
 
-                        if current_metadata.version() != c.meta.version() {
-                            infoln!("Cache is at different version");
+                        if current_metadata.schema_version() != c.meta.schema_version() {
+                            infoln!("Cache is at different schema version");
                             infoln!(
                                 entry: decorator::Entry::None,
-                                "Previous version is {}",
-                                c.meta.version().bold()
+                                "Previous schema version is {}",
+                                c.meta.schema_version().to_string().bold()
                             );
                         }
 
@@ -591,15 +649,14 @@ impl Processor for CacheImporterProcessor {
                     }
                 }
                 Err(e) => {
-                    match e.downcast_ref::<super::LoadError>().unwrap() {
-                        super::LoadError::FileNotFound(_path) => {
+                    match e.downcast_ref::<super::LoadError>() {
+                        Some(super::LoadError::FileNotFound(_path)) => {
                             warnln!("Cache file not found at expected path");
                             infoln!(block, "Creating a new one");
                         }
-                        super::LoadError::Deserialize(serde_json_error) => {
+                        Some(super::LoadError::Deserialize(serde_json_error)) => {
                             match serde_json_error.classify() {
-                                serde_json::error::Category::Io
-                                | serde_json::error::Category::Eof => {
+                                serde_json::error::Category::Io => {
                                     panic!("Cache file io error: {}", e);
                                 }
                                 _ => {
@@ -609,6 +666,15 @@ impl Processor for CacheImporterProcessor {
                                 }
                             }
                         }
+                        Some(
+                            load_error @ (super::LoadError::UnsupportedSchemaVersion(_)
+                            | super::LoadError::Migration { .. }),
+                        ) => {
+                            warnln!("Cache file can't be used: {}", load_error);
+                            infoln!("A new one will be used instead");
+                            infoln!(block, "Creating a new one");
+                        }
+                        None => panic!("Cache file io error: {}", e),
                     }
 
                     state_cache = self.initialize_cache(state, current_metadata);
@@ -617,6 +683,12 @@ impl Processor for CacheImporterProcessor {
             }
         }
 
+        // packer needs every graphic to repack,
+        // they're retrieved from cache instead of processed again
+        if state_cache.is_stage_outdated("Packer") {
+            state.graphic_output.request();
+        }
+
         state.cache.replace(state_cache);
         doneln_with_timer!(total_timer);
     }
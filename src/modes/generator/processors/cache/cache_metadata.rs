@@ -1,16 +1,27 @@
 use serde::{Deserialize, Serialize};
-use std::time::SystemTime;
+use std::{collections::BTreeMap, time::SystemTime};
+
+use crate::settings::{GraphicKey, ImageFormat, OutputConfig, Pivot, TextureCompression};
 
 #[derive(PartialEq, Serialize, Deserialize, Debug)]
 pub struct CacheMetadata {
     version: String,
+
+    /// Cache file format version, older ones are migrated when loaded.
+    schema_version: u32,
+
     generation: GenerationMetadata,
 }
 
 impl CacheMetadata {
+    /// Current cache file format version.
+    /// It changes, alongside a new migration, whenever cache format or it's meaning changes.
+    pub const SCHEMA_VERSION: u32 = 2;
+
     pub fn new(generation: GenerationMetadata) -> Self {
         Self {
             version: env!("CARGO_PKG_VERSION").to_owned(),
+            schema_version: CacheMetadata::SCHEMA_VERSION,
             generation,
         }
     }
@@ -19,6 +30,10 @@ impl CacheMetadata {
         &self.version
     }
 
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
     pub fn generation_metadata(&self) -> &GenerationMetadata {
         &self.generation
     }
@@ -28,16 +43,69 @@ impl CacheMetadata {
 pub struct GenerationMetadata {
     pub image: ImageOutputMetadata,
     pub data: DataOutputMetadata,
+
+    /// Config fingerprint by processor name.
+    #[serde(default)]
+    pub fingerprints: BTreeMap<String, u64>,
 }
 
 #[derive(PartialEq, Serialize, Deserialize, Debug)]
 pub struct ImageOutputMetadata {
     pub source_directory_modtime: SystemTime,
+
+    /// Hash of every file path at source directory, at any depth.
+    #[serde(default)]
+    pub source_files_hash: u64,
+
     pub width: u32,
     pub height: u32,
+
+    #[serde(default)]
+    pub premultiplied_alpha: bool,
+
+    #[serde(default)]
+    pub alpha_bleed: bool,
+
+    #[serde(default = "ImageOutputMetadata::default_alignment")]
+    pub alignment: u32,
+
+    #[serde(default = "OutputConfig::default_image_formats")]
+    pub formats: Vec<ImageFormat>,
+
+    #[serde(default)]
+    pub compression: TextureCompression,
+
+    #[serde(default)]
+    pub png_optimization: u8,
+}
+
+impl ImageOutputMetadata {
+    pub fn default_alignment() -> u32 {
+        1
+    }
 }
 
 #[derive(PartialEq, Serialize, Deserialize, Debug)]
 pub struct DataOutputMetadata {
     pub prettified: bool,
+
+    #[serde(default)]
+    pub companions: Vec<String>,
+
+    /// Older caches were always keyed by name.
+    #[serde(default = "DataOutputMetadata::default_graphic_key")]
+    pub graphic_key: GraphicKey,
+
+    #[serde(default)]
+    pub default_pivot: Option<Pivot>,
+
+    /// Older caches didn't register it.
+    #[serde(default)]
+    pub format_version: Option<u32>,
+}
+
+impl DataOutputMetadata {
+    fn default_graphic_key() -> GraphicKey {
+        GraphicKey::Name
+    }
 }
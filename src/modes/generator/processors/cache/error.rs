@@ -37,13 +37,17 @@ impl Display for Error {
 pub enum LoadError {
     Deserialize(serde_json::error::Error),
     FileNotFound(PathBuf),
+    UnsupportedSchemaVersion(u32),
+    Migration { schema_version: u32, reason: String },
 }
 
 impl error::Error for LoadError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match &self {
             LoadError::Deserialize(json_error) => Some(json_error),
-            LoadError::FileNotFound(_path) => None,
+            LoadError::FileNotFound(_)
+            | LoadError::UnsupportedSchemaVersion(_)
+            | LoadError::Migration { .. } => None,
         }
     }
 }
@@ -57,6 +61,20 @@ impl Display for LoadError {
                 json_error
             ),
             LoadError::FileNotFound(path) => write!(f, "File not found at '{}'", path.display()),
+            LoadError::UnsupportedSchemaVersion(schema_version) => write!(
+                f,
+                "Schema version {} is newer than supported one ({})",
+                schema_version,
+                super::CacheMetadata::SCHEMA_VERSION
+            ),
+            LoadError::Migration {
+                schema_version,
+                reason,
+            } => write!(
+                f,
+                "Can't migrate from schema version {}: {}",
+                schema_version, reason
+            ),
         }
     }
 }
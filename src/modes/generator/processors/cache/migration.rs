@@ -0,0 +1,169 @@
+use serde_json::Value;
+
+use super::{CacheMetadata, LoadError};
+
+/// Values which a migration may need, but an older cache doesn't hold.
+pub struct MigrationContext {
+    /// Hash of current settings which source files are processed with.
+    pub config_hash: u64,
+}
+
+type Migration = fn(&mut Value, &MigrationContext) -> Result<(), String>;
+
+/// Migrations by schema version they upgrade from, starting at version 1.
+/// A format change which can't be expressed as a migration may just discard `files`.
+const MIGRATIONS: [Migration; 1] = [v1_to_v2];
+
+/// Cache files without a schema version are at it.
+const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+/// Upgrades a cache json value, in place, to current schema version.
+/// Returns schema version which it was at.
+pub fn migrate(cache: &mut Value, context: &MigrationContext) -> Result<u32, LoadError> {
+    let schema_version = match cache.pointer("/meta/schema_version") {
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| LoadError::Migration {
+                schema_version: LEGACY_SCHEMA_VERSION,
+                reason: format!("schema version '{}' isn't a valid one", version),
+            })?,
+        None => LEGACY_SCHEMA_VERSION,
+    };
+
+    if schema_version > CacheMetadata::SCHEMA_VERSION {
+        return Err(LoadError::UnsupportedSchemaVersion(schema_version));
+    }
+
+    for version in schema_version..CacheMetadata::SCHEMA_VERSION {
+        let migration = MIGRATIONS[(version - LEGACY_SCHEMA_VERSION) as usize];
+
+        migration(cache, context).map_err(|reason| LoadError::Migration {
+            schema_version: version,
+            reason,
+        })?;
+
+        match cache.get_mut("meta") {
+            Some(Value::Object(meta)) => {
+                meta.insert("schema_version".to_owned(), Value::from(version + 1));
+            }
+            _ => {
+                return Err(LoadError::Migration {
+                    schema_version: version,
+                    reason: "metadata is missing".to_owned(),
+                })
+            }
+        }
+    }
+
+    Ok(schema_version)
+}
+
+/// Entries are verified against settings which they were processed with.
+/// Older entries doesn't know them, so they're assumed to be current ones.
+fn v1_to_v2(cache: &mut Value, context: &MigrationContext) -> Result<(), String> {
+    let files = match cache.get_mut("files") {
+        Some(Value::Object(files)) => files,
+        _ => return Err("entries are missing".to_owned()),
+    };
+
+    for (location, entry) in files.iter_mut() {
+        match entry {
+            Value::Object(entry) => {
+                entry
+                    .entry("config_hash")
+                    .or_insert_with(|| Value::from(context.config_hash));
+            }
+            _ => return Err(format!("entry '{}' isn't an object", location)),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use std::path::Path;
+
+    use super::*;
+    use crate::modes::generator::processors::cache::Cache;
+
+    const CONTEXT: MigrationContext = MigrationContext { config_hash: 7 };
+
+    /// Cache file as written before schema was versioned.
+    fn legacy_cache() -> Value {
+        json!({
+            "meta": {
+                "version": "0.5.0",
+                "generation": {
+                    "image": {
+                        "source_directory_modtime": {
+                            "secs_since_epoch": 0,
+                            "nanos_since_epoch": 0
+                        },
+                        "width": 64,
+                        "height": 64
+                    },
+                    "data": { "prettified": false }
+                }
+            },
+            "files": {
+                "hero": {
+                    "modtime": { "secs_since_epoch": 0, "nanos_since_epoch": 0 },
+                    "extension": "aseprite",
+                    "data": {
+                        "frames": [
+                            {
+                                "atlas": { "x": 0, "y": 0, "width": 8, "height": 8 },
+                                "duration": 100,
+                                "source": { "x": 0, "y": 0, "width": 8, "height": 8 }
+                            },
+                            null
+                        ],
+                        "tracks": []
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn legacy_cache_is_upgraded() {
+        let mut cache = legacy_cache();
+
+        assert_eq!(migrate(&mut cache, &CONTEXT).unwrap(), 1);
+        assert_eq!(
+            cache["meta"]["schema_version"],
+            CacheMetadata::SCHEMA_VERSION
+        );
+
+        let cache: Cache = serde_json::from_value(cache).unwrap();
+        assert_eq!(cache.meta.schema_version(), CacheMetadata::SCHEMA_VERSION);
+        assert_eq!(cache.files[Path::new("hero")].borrow().config_hash, 7);
+    }
+
+    #[test]
+    fn newer_cache_is_unsupported() {
+        let mut cache = legacy_cache();
+        cache["meta"]["schema_version"] = Value::from(CacheMetadata::SCHEMA_VERSION + 1);
+
+        assert!(matches!(
+            migrate(&mut cache, &CONTEXT),
+            Err(LoadError::UnsupportedSchemaVersion(_))
+        ));
+    }
+
+    #[test]
+    fn malformed_cache_fails_to_migrate() {
+        let mut cache = json!({ "meta": {} });
+
+        assert!(matches!(
+            migrate(&mut cache, &CONTEXT),
+            Err(LoadError::Migration {
+                schema_version: 1,
+                ..
+            })
+        ));
+    }
+}
@@ -1,8 +1,8 @@
 use std::{
     cell::RefCell,
     cmp::Eq,
-    collections::HashMap,
-    fs::{File, Metadata, OpenOptions},
+    collections::{HashMap, HashSet},
+    fs::{File, OpenOptions},
     hash::Hash,
     io::{self, BufReader, BufWriter, Write},
     path::{Path, PathBuf},
@@ -16,6 +16,7 @@ mod cache_importer_processor;
 mod cache_metadata;
 mod cache_status;
 mod error;
+mod migration;
 
 pub use cache_entry::CacheEntry;
 pub use cache_exporter_processor::CacheExporterProcessor;
@@ -27,8 +28,9 @@ pub use cache_metadata::{
 
 pub use cache_status::CacheStatus;
 pub use error::{Error, LoadError, SaveError};
+pub use migration::MigrationContext;
 
-use crate::modes::generator::processors::data::GraphicData;
+use crate::{modes::generator::processors::data::GraphicData, settings::CacheValidation, util};
 
 #[derive(Serialize, Deserialize)]
 pub struct Cache {
@@ -43,8 +45,24 @@ pub struct Cache {
     #[serde(skip)]
     pub atlas_output_path: PathBuf,
 
+    /// How entries are verified against their source files.
+    #[serde(skip)]
+    pub validation: CacheValidation,
+
+    /// Hash of current settings which source files are processed with.
+    #[serde(skip)]
+    pub config_hash: u64,
+
     #[serde(skip)]
     outdated: bool,
+
+    /// Schema version which cache file was at, when it was migrated.
+    #[serde(skip)]
+    migrated_from: Option<u32>,
+
+    /// Processors which config has changed since cache was generated.
+    #[serde(skip)]
+    outdated_stages: HashSet<String>,
 }
 
 impl Cache {
@@ -54,7 +72,11 @@ impl Cache {
             files: HashMap::new(),
             images_path,
             atlas_output_path,
+            validation: CacheValidation::default(),
+            config_hash: 0,
             outdated: false,
+            migrated_from: None,
+            outdated_stages: HashSet::new(),
         }
     }
 
@@ -66,10 +88,20 @@ impl Cache {
         file: &File,
         images_path: P,
         atlas_output_path: P,
+        migration_context: &MigrationContext,
     ) -> eyre::Result<Self> {
         let buf_reader = BufReader::new(file);
-        match serde_json::from_reader::<_, Cache>(buf_reader) {
+        let mut value = serde_json::from_reader::<_, serde_json::Value>(buf_reader)
+            .map_err(LoadError::Deserialize)?;
+
+        let schema_version = migration::migrate(&mut value, migration_context)?;
+
+        match serde_json::from_value::<Cache>(value) {
             Ok(mut c) => {
+                if schema_version != CacheMetadata::SCHEMA_VERSION {
+                    c.migrated_from = Some(schema_version);
+                }
+
                 c.images_path = images_path.into();
                 c.atlas_output_path = atlas_output_path.into();
 
@@ -90,9 +122,10 @@ impl Cache {
         filepath: P,
         images_path: T,
         atlas_output_path: T,
+        migration_context: &MigrationContext,
     ) -> eyre::Result<Self> {
         match OpenOptions::new().read(true).open(&filepath) {
-            Ok(file) => Self::load(&file, images_path, atlas_output_path),
+            Ok(file) => Self::load(&file, images_path, atlas_output_path, migration_context),
             Err(e) => match e.kind() {
                 io::ErrorKind::NotFound => Err(eyre::Error::from(LoadError::FileNotFound(
                     filepath.as_ref().to_owned(),
@@ -114,6 +147,7 @@ impl Cache {
         let mut file = OpenOptions::new()
             .write(true)
             .append(false)
+            .truncate(true)
             .create(true)
             .open(filepath)
             .unwrap();
@@ -133,6 +167,7 @@ impl Cache {
         let mut file = OpenOptions::new()
             .write(true)
             .append(false)
+            .truncate(true)
             .create(true)
             .open(filepath)
             .unwrap();
@@ -143,17 +178,20 @@ impl Cache {
     pub fn retrieve<'r, P: AsRef<Path> + Eq + Hash>(
         &'r self,
         location: P,
-        source_metadata: &Metadata,
+        source_path: &Path,
     ) -> CacheStatus<'r> {
-        let source_modtime = source_metadata.modified().unwrap();
-
         match self.files.get(location.as_ref()) {
             Some(cache_file) => {
                 let cache = cache_file.borrow();
-                if cache.modtime.eq(&source_modtime) {
-                    CacheStatus::Found(cache)
-                } else {
-                    CacheStatus::Outdated
+
+                match cache.is_up_to_date(source_path, self.validation, self.config_hash) {
+                    Ok(true) => CacheStatus::Found(cache),
+                    Ok(false) => CacheStatus::Outdated,
+                    Err(e) => panic!(
+                        "Can't verify source file '{}': {}",
+                        source_path.display(),
+                        e
+                    ),
                 }
             }
             None => CacheStatus::NotFound,
@@ -164,15 +202,21 @@ impl Cache {
         &mut self,
         location: P,
         extension: String,
-        metadata: &Metadata,
+        source_path: &Path,
         data: GraphicData,
     ) -> eyre::Result<()> {
-        let modtime = metadata.modified().unwrap();
+        let modtime = source_path.metadata()?.modified()?;
+        let content_hash = match self.validation {
+            CacheValidation::Modtime => None,
+            CacheValidation::Content => Some(util::hash::file_fnv1a_64(source_path)?),
+        };
 
         self.files.insert(
             location.as_ref().to_owned(),
             RefCell::new(CacheEntry::new(
                 modtime,
+                content_hash,
+                self.config_hash,
                 extension,
                 data,
                 location.as_ref().to_owned(),
@@ -182,6 +226,10 @@ impl Cache {
         Ok(())
     }
 
+    pub fn migrated_from(&self) -> Option<u32> {
+        self.migrated_from
+    }
+
     pub fn is_outdated(&self) -> bool {
         self.outdated
     }
@@ -190,6 +238,14 @@ impl Cache {
         !self.outdated
     }
 
+    pub fn is_stage_outdated(&self, stage: &str) -> bool {
+        self.outdated_stages.contains(stage)
+    }
+
+    pub fn mark_stage_as_outdated(&mut self, stage: &str) {
+        self.outdated_stages.insert(stage.to_owned());
+    }
+
     pub fn mark_as_outdated(&mut self) {
         if self.is_outdated() {
             return;
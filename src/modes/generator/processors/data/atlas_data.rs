@@ -1,63 +1,188 @@
 use std::{
-    collections::HashMap,
-    fs::{File, OpenOptions},
+    collections::BTreeMap,
     io::{BufWriter, Write},
-    path::Path,
 };
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use super::{GraphicData, MetaData, SaveError};
 
 #[derive(Serialize, Deserialize)]
 pub struct AtlasData {
-    pub graphics: HashMap<String, GraphicData>,
+    /// Graphics ordered by key, exporters rely on it to write a reproducible output.
+    pub graphics: BTreeMap<String, GraphicData>,
     pub meta: MetaData,
 }
 
 impl AtlasData {
     pub fn new() -> Self {
         Self {
-            graphics: HashMap::new(),
+            graphics: BTreeMap::new(),
             meta: MetaData::new(),
         }
     }
 
-    pub fn save(&self, file: &mut File) -> eyre::Result<()> {
-        let mut buf_writer = BufWriter::new(file);
-        serde_json::to_writer(&mut buf_writer, &self).map_err(SaveError::Serialize)?;
-        buf_writer.flush().unwrap();
+    /// Copy with every graphic's regions multiplied by an integer scale factor.
+    pub fn scaled(&self, scale: u32) -> Self {
+        Self {
+            graphics: self
+                .graphics
+                .iter()
+                .map(|(name, graphic)| (name.clone(), graphic.scaled(scale)))
+                .collect(),
+            meta: MetaData::new(),
+        }
+    }
 
-        Ok(())
+    /// Json value with graphics nested into an object per key path segment,
+    /// e.g `characters/hero` is at `graphics.characters.hero`.
+    pub fn nested(&self) -> Result<Value, SaveError> {
+        let mut graphics = Map::new();
+        for (key, graphic) in self.graphics.iter() {
+            let mut segments: Vec<&str> = key.split('/').collect();
+            let name = segments.pop().unwrap_or_default();
+            let mut directory = &mut graphics;
+
+            for segment in segments {
+                directory = match directory
+                    .entry(segment)
+                    .or_insert_with(|| Value::Object(Map::new()))
+                {
+                    Value::Object(entries) => entries,
+                    _ => panic!("Graphic '{}' is at a directory place", segment),
+                };
+            }
+
+            directory.insert(
+                name.to_owned(),
+                serde_json::to_value(graphic).map_err(SaveError::Serialize)?,
+            );
+        }
+
+        let mut atlas_data = Map::new();
+        atlas_data.insert("graphics".to_owned(), Value::Object(graphics));
+        atlas_data.insert(
+            "meta".to_owned(),
+            serde_json::to_value(&self.meta).map_err(SaveError::Serialize)?,
+        );
+
+        Ok(Value::Object(atlas_data))
     }
 
-    pub fn save_to_path<P: AsRef<Path>>(&self, filepath: P) -> eyre::Result<()> {
-        let mut file = OpenOptions::new()
-            .write(true)
-            .append(false)
-            .create(true)
-            .open(filepath)
-            .unwrap();
+    /// Json value at a data format version, older versions lack every field
+    /// which was introduced after them.
+    pub fn versioned(&self, format_version: u32, nested: bool) -> Result<Value, SaveError> {
+        let mut value = if nested {
+            self.nested()?
+        } else {
+            serde_json::to_value(self).map_err(SaveError::Serialize)?
+        };
+
+        if format_version <= MetaData::LEGACY_FORMAT_VERSION {
+            if let Some(Value::Object(meta)) = value.get_mut("meta") {
+                meta.retain(|key, _| key == "app" || key == "version");
+            }
+
+            if let Some(graphics) = value.get_mut("graphics") {
+                downgrade_to_legacy(graphics);
+            }
+        }
 
-        self.save(&mut file)
+        Ok(value)
     }
 
-    pub fn save_pretty(&self, file: &mut File) -> eyre::Result<()> {
-        let mut buf_writer = BufWriter::new(file);
+    pub fn save<W: Write>(&self, writer: W) -> eyre::Result<()> {
+        let mut buf_writer = BufWriter::new(writer);
+        serde_json::to_writer(&mut buf_writer, &self).map_err(SaveError::Serialize)?;
+        buf_writer.flush().unwrap();
+
+        Ok(())
+    }
+
+    pub fn save_pretty<W: Write>(&self, writer: W) -> eyre::Result<()> {
+        let mut buf_writer = BufWriter::new(writer);
         serde_json::to_writer_pretty(&mut buf_writer, &self).map_err(SaveError::Serialize)?;
         buf_writer.flush().unwrap();
 
         Ok(())
     }
+}
 
-    pub fn save_pretty_to_path<P: AsRef<Path>>(&self, filepath: P) -> eyre::Result<()> {
-        let mut file = OpenOptions::new()
-            .write(true)
-            .append(false)
-            .create(true)
-            .open(filepath)
-            .unwrap();
+/// Remove every graphic and frame field which legacy format doesn't have,
+/// graphics are found at any directory depth.
+fn downgrade_to_legacy(directory: &mut Value) {
+    let entries = match directory {
+        Value::Object(entries) => entries,
+        _ => return,
+    };
+
+    for entry in entries.values_mut() {
+        let is_graphic = matches!(entry.get("frames"), Some(Value::Array(_)));
+
+        if !is_graphic {
+            downgrade_to_legacy(entry);
+            continue;
+        }
+
+        if let Value::Object(graphic) = entry {
+            graphic.remove("canvas");
+
+            if let Some(Value::Array(frames)) = graphic.get_mut("frames") {
+                for frame in frames.iter_mut() {
+                    if let Value::Object(frame) = frame {
+                        frame.remove("pivot");
+                        frame.remove("hitboxes");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Size;
+
+    fn atlas_data(names: &[&str]) -> AtlasData {
+        let mut atlas_data = AtlasData::new();
 
-        self.save_pretty(&mut file)
+        for name in names {
+            let mut graphic = GraphicData::new();
+            graphic.canvas = Some(Size::new(name.len() as u32, 1));
+            atlas_data.graphics.insert(name.to_string(), graphic);
+        }
+
+        atlas_data
+    }
+
+    #[test]
+    fn save_is_independent_of_insertion_order() {
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+
+        atlas_data(&["b", "a/c", "a"]).save(&mut first).unwrap();
+        atlas_data(&["a", "a/c", "b"]).save(&mut second).unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn legacy_version_lacks_newer_fields() {
+        let data = atlas_data(&["a", "b/c"]);
+
+        let latest = data.versioned(MetaData::FORMAT_VERSION, true).unwrap();
+        assert_eq!(latest["meta"]["format_version"], MetaData::FORMAT_VERSION);
+        assert!(latest["graphics"]["b"]["c"].get("canvas").is_some());
+
+        let legacy = data
+            .versioned(MetaData::LEGACY_FORMAT_VERSION, true)
+            .unwrap();
+        assert!(legacy["meta"].get("format_version").is_none());
+        assert!(legacy["meta"].get("image").is_none());
+        assert!(legacy["graphics"]["a"].get("canvas").is_none());
+        assert!(legacy["graphics"]["b"]["c"].get("canvas").is_none());
+        assert!(legacy["graphics"]["b"]["c"]["frames"].is_array());
     }
 }
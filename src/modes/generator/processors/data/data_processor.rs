@@ -1,25 +1,321 @@
 use colored::Colorize;
-use std::fs;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, iter,
+    path::{Path, PathBuf},
+};
 
 use crate::{
     common::Verbosity,
+    math::{Point, Size},
     modes::generator::processors::{
-        output::{self, OutputFile},
+        cache::Cache,
+        output::{self, Output, OutputFile},
         ConfigStatus, Processor, State,
     },
-    settings::{Config, ProcessorConfig},
+    settings::{Config, DataConfig, GraphicKey, Pivot, ProcessorConfig},
     util::{self, Timer},
 };
 
-use super::AtlasData;
+use super::{
+    exporters::{DataExporter, ExportContext, ExportFiles, TemplateExporter},
+    AtlasData, Error, FrameData, GraphicData, MetaData, PivotData, TextureData,
+};
+
+/// Extension of a source's sidecar file which defines it's pivot point, e.g `hero.pivot`.
+const PIVOT_SIDECAR_EXTENSION: &str = "pivot";
 
 pub struct DataProcessor {
     verbose: bool,
+    exporters: Vec<Box<dyn DataExporter>>,
+    templates: Vec<TemplateExporter>,
 }
 
 impl DataProcessor {
     pub fn new() -> Self {
-        Self { verbose: false }
+        Self {
+            verbose: false,
+            exporters: Vec::new(),
+            templates: Vec::new(),
+        }
+    }
+
+    pub fn register_exporter<E: DataExporter + 'static>(&mut self, exporter: E) -> &mut Self {
+        self.exporters.push(Box::new(exporter));
+        self
+    }
+
+    fn exporter(&self, format: &str) -> Option<&dyn DataExporter> {
+        self.exporters
+            .iter()
+            .find(|e| e.name() == format)
+            .map(|e| e.as_ref())
+    }
+
+    /// Exporters selected at config formats, followed by templates.
+    fn selected_exporters(&self, config: &Config) -> Vec<&dyn DataExporter> {
+        config
+            .data
+            .formats
+            .iter()
+            .map(|format| self.exporter(format).unwrap())
+            .chain(self.templates.iter().map(|t| t as &dyn DataExporter))
+            .collect()
+    }
+
+    /// Every atlas image file generated to a scale.
+    fn textures(&self, config: &Config, scale: u32) -> Vec<TextureData> {
+        let companions = iter::once("").chain(
+            config
+                .image
+                .companions
+                .iter()
+                .map(String::as_str)
+                .filter(|suffix| !suffix.is_empty()),
+        );
+
+        companions
+            .flat_map(|companion| {
+                config
+                    .output
+                    .image_formats
+                    .iter()
+                    .map(move |format| TextureData {
+                        image: format!(
+                            "{}.{}",
+                            config.output.page_name(companion, scale),
+                            format.extension()
+                        ),
+                        format: *format,
+                        compression: if format.is_compressed() {
+                            Some(config.output.compression)
+                        } else {
+                            None
+                        },
+                        companion: if companion.is_empty() {
+                            None
+                        } else {
+                            Some(companion.to_owned())
+                        },
+                    })
+            })
+            .collect()
+    }
+
+    /// Data format version to be emitted, 0 stands for latest.
+    pub fn resolved_format_version(config: &DataConfig) -> u32 {
+        match config.format_version {
+            0 => MetaData::FORMAT_VERSION,
+            version => version,
+        }
+    }
+
+    /// Graphic key kind in use, legacy format version always keys graphics by name.
+    pub fn resolved_graphic_key(config: &DataConfig) -> GraphicKey {
+        if Self::resolved_format_version(config) <= MetaData::LEGACY_FORMAT_VERSION {
+            GraphicKey::Name
+        } else {
+            config.graphic_key
+        }
+    }
+
+    /// Graphic key from it's location, relative to input path and without extension.
+    fn graphic_key(config: &Config, location: &Path) -> String {
+        match Self::resolved_graphic_key(&config.data) {
+            GraphicKey::Name => match location.file_stem() {
+                Some(location_stem) => location_stem.to_str().unwrap().to_owned(),
+                None => panic!("File stem not found at location '{}'", location.display()),
+            },
+            GraphicKey::Path | GraphicKey::Nested => location
+                .components()
+                .map(|c| c.as_os_str().to_str().unwrap())
+                .collect::<Vec<&str>>()
+                .join("/"),
+        }
+    }
+
+    fn pivot_sidecar_path(config: &Config, location: &Path) -> PathBuf {
+        let mut path = Path::new(&config.image.input_path)
+            .join(location)
+            .into_os_string();
+
+        path.push(".");
+        path.push(PIVOT_SIDECAR_EXTENSION);
+        path.into()
+    }
+
+    /// Pivot defined at a sidecar file, by it's name (e.g `bottom-center`) or coordinates (e.g `8, 16`).
+    fn read_pivot_sidecar(path: &Path, canvas: &Size<u32>) -> Option<Point<i32>> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                warnln!("Can't read pivot file '{}': {}", path.display(), e);
+                return None;
+            }
+        };
+
+        let contents = contents.trim();
+
+        if let Some(pivot) = Pivot::from_name(contents) {
+            return Some(pivot.position(canvas));
+        }
+
+        let coordinates: Vec<&str> = contents
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|c| !c.is_empty())
+            .collect();
+
+        if let [x, y] = coordinates[..] {
+            if let (Ok(x), Ok(y)) = (x.parse(), y.parse()) {
+                return Some(Point::new(x, y));
+            }
+        }
+
+        warnln!(
+            "Pivot file '{}' should contain a pivot name or 'x, y' coordinates",
+            path.display()
+        );
+
+        None
+    }
+
+    /// Defines pivot at every frame which source format didn't provide one,
+    /// from source's pivot sidecar file or, if there is none, default pivot.
+    fn resolve_pivots(config: &Config, location: &Path, data: &mut GraphicData) {
+        let canvas = data.canvas_size();
+        let sidecar_path = Self::pivot_sidecar_path(config, location);
+
+        let pivot = if sidecar_path.is_file() {
+            Self::read_pivot_sidecar(&sidecar_path, &canvas)
+        } else {
+            None
+        }
+        .or_else(|| config.image.default_pivot.map(|p| p.position(&canvas)));
+
+        if let Some(pivot) = pivot {
+            for frame in data.frames.iter_mut() {
+                if let FrameData::Contents {
+                    source_region,
+                    pivot: frame_pivot @ None,
+                    ..
+                } = frame
+                {
+                    *frame_pivot = Some(PivotData::new(pivot.clone(), source_region));
+                }
+            }
+        }
+    }
+
+    /// Graphics' data by key, companion graphics are left out.
+    fn gather_graphics(
+        &self,
+        config: &Config,
+        cache: &Cache,
+    ) -> Result<BTreeMap<String, GraphicData>, Error> {
+        let mut graphics = BTreeMap::new();
+        let mut key_locations: HashMap<String, &Path> = HashMap::new();
+
+        let mut locations: Vec<&PathBuf> = cache.files.keys().collect();
+        locations.sort();
+
+        for location in locations {
+            // companion graphics shares their base graphic data
+            if let Some((base_location, _)) = config.image.companion_base(location) {
+                if cache.files.contains_key(&base_location) {
+                    continue;
+                }
+            }
+
+            let key = Self::graphic_key(config, location);
+
+            if let Some(other_location) = key_locations.get(&key) {
+                return Err(Error::DuplicateGraphicKey {
+                    key,
+                    locations: (other_location.to_path_buf(), location.clone()),
+                });
+            }
+
+            let mut data = cache.files[location].borrow().data.clone();
+            Self::resolve_pivots(config, location, &mut data);
+
+            graphics.insert(key.clone(), data);
+            key_locations.insert(key, location);
+        }
+
+        if Self::resolved_graphic_key(&config.data) == GraphicKey::Nested {
+            // a graphic can't be at a directory's place
+            for (key, location) in &key_locations {
+                let mut parent = key.as_str();
+
+                while let Some((p, _)) = parent.rsplit_once('/') {
+                    parent = p;
+
+                    if let Some(parent_location) = key_locations.get(parent) {
+                        return Err(Error::DuplicateGraphicKey {
+                            key: parent.to_owned(),
+                            locations: (parent_location.to_path_buf(), location.to_path_buf()),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(graphics)
+    }
+
+    fn register_output_files(
+        &self,
+        output: &mut Output,
+        config: &Config,
+        atlas_data: &AtlasData,
+        scales: &[u32],
+    ) -> eyre::Result<()> {
+        for exporter in self.selected_exporters(config) {
+            for scale in scales {
+                let atlas_name = config.output.scaled_name(*scale);
+
+                for filename in exporter.filenames(&atlas_name, atlas_data) {
+                    let output_file = OutputFile::new(config.cache.atlas_path().join(filename));
+                    output.register_file(output_file)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Atlas image which data should refer to, uncompressed formats are preferred.
+    fn main_texture(textures: &[TextureData]) -> Option<&TextureData> {
+        let pages = textures.iter().filter(|t| t.companion.is_none());
+
+        pages
+            .clone()
+            .find(|t| !t.format.is_compressed())
+            .or_else(|| pages.clone().next())
+    }
+
+    /// Hash of atlas image decoded pixels, compressed formats can't be decoded.
+    fn image_hash(&self, config: &Config, texture: &TextureData) -> Option<String> {
+        if texture.format.is_compressed() {
+            return None;
+        }
+
+        let image_path = config.cache.atlas_path().join(&texture.image);
+
+        match image::open(&image_path) {
+            Ok(image) => Some(format!(
+                "{:016x}",
+                util::hash::fnv1a_64(image.to_rgba8().as_raw())
+            )),
+            Err(e) => {
+                warnln!(
+                    "Can't hash atlas image at '{}': {}",
+                    image_path.display(),
+                    e
+                );
+                None
+            }
+        }
     }
 }
 
@@ -32,6 +328,29 @@ impl Processor for DataProcessor {
         Some(&config.data)
     }
 
+    fn config_fingerprint(&self, config: &Config) -> Option<u64> {
+        Some(util::hash::fingerprint(&(
+            (
+                config.data.prettify,
+                Self::resolved_graphic_key(&config.data),
+                Self::resolved_format_version(&config.data),
+                &config.data.formats,
+                &config.data.templates,
+            ),
+            (
+                &config.output.name,
+                config.output.premultiply_alpha,
+                &config.output.image_formats,
+                config.output.compression,
+            ),
+            (
+                &config.image.companions,
+                config.image.default_pivot,
+                &config.image.scale,
+            ),
+        )))
+    }
+
     fn setup(&mut self, state: &mut State) -> ConfigStatus {
         let mut c = state
             .config
@@ -39,6 +358,65 @@ impl Processor for DataProcessor {
             .expect("Can't retrieve a write lock");
 
         c.data.prettify = c.data.prettify || c.prettify;
+
+        if c.data.formats.is_empty() {
+            panic!("At least one data format should be provided");
+        }
+
+        for format in &c.data.formats {
+            if self.exporter(format).is_none() {
+                let available: Vec<&str> = self.exporters.iter().map(|e| e.name()).collect();
+                panic!(
+                    "{}, available formats are: {}",
+                    Error::UnknownFormat(format.clone()),
+                    available.join(", ")
+                );
+            }
+        }
+
+        let format_version = Self::resolved_format_version(&c.data);
+
+        if format_version > MetaData::FORMAT_VERSION {
+            panic!("{}", Error::UnsupportedFormatVersion(format_version));
+        }
+
+        if Self::resolved_graphic_key(&c.data) != c.data.graphic_key {
+            warnln!(
+                "Data format version {} keys graphics by name, data.graphic_key is ignored",
+                format_version
+            );
+        }
+
+        self.templates = c
+            .data
+            .templates
+            .iter()
+            .map(|path| {
+                TemplateExporter::load(path).unwrap_or_else(|e| {
+                    panic!("Can't load template '{}': {}", path, e.root_cause())
+                })
+            })
+            .collect();
+
+        // every template writes to a file named after it's stem
+        for (i, template) in self.templates.iter().enumerate() {
+            if let Some(other) = self.templates[..i]
+                .iter()
+                .position(|t| t.suffix() == template.suffix())
+            {
+                panic!(
+                    "{}",
+                    Error::DuplicateTemplateOutput {
+                        suffix: template.suffix().to_owned(),
+                        templates: (
+                            PathBuf::from(&c.data.templates[other]),
+                            PathBuf::from(&c.data.templates[i])
+                        ),
+                    }
+                );
+            }
+        }
+
         ConfigStatus::NotModified
     }
 
@@ -49,95 +427,93 @@ impl Processor for DataProcessor {
         let total_timer = Timer::start();
         let mut atlas_data = AtlasData::new();
 
-        let cache = match &state.cache {
-            Some(cache) => {
-                if cache.is_updated()
-                    && cache.meta.generation_metadata().data.prettified == c.data.prettify
-                {
-                    // output file
-                    let output_atlas_data_path = c
-                        .cache
-                        .atlas_path()
-                        .join(format!("{}.data.json", c.output.name_or_default()));
-
-                    let output_file = OutputFile::new(output_atlas_data_path);
-
-                    match state.output.register_file(output_file) {
-                        Ok(()) => {
-                            infoln!(last, "{}", "Already Updated".green());
-                            return;
-                        }
-                        Err(e) => match e.downcast_ref::<output::Error>().unwrap() {
-                            output::Error::FileExpected => {
-                                infoln!("Output file not found, regenerating it")
-                            }
-                            _ => Err(e).unwrap(),
-                        },
-                    }
-                }
+        let scales = c.image.scale.factors();
 
-                cache
-            }
+        let cache = match &state.cache {
+            Some(cache) => cache,
             None => panic!("Cache isn't available"),
         };
 
         infoln!(block, "Gathering graphics' data entries");
         let gathering_graphics_timer = Timer::start();
+        atlas_data.graphics = match self.gather_graphics(&c, cache) {
+            Ok(graphics) => graphics,
+            Err(e) => panic!("{}", e),
+        };
+        doneln_with_timer!(gathering_graphics_timer);
 
-        for entry in cache.files.values() {
-            match entry.borrow().location.file_stem() {
-                Some(location_stem) => {
-                    atlas_data.graphics.insert(
-                        location_stem.to_str().unwrap().to_owned(),
-                        entry.borrow().data.clone(),
-                    );
-                }
-                None => {
-                    panic!(
-                        "File stem not found at location '{}'",
-                        entry.borrow().location.display()
-                    );
+        // templates and pivot files may have been changed since last run,
+        // so they're always handled
+        if cache.is_updated()
+            && c.data.templates.is_empty()
+            && !cache.is_stage_outdated(self.name())
+            && !cache
+                .files
+                .keys()
+                .any(|location| Self::pivot_sidecar_path(&c, location).is_file())
+        {
+            match self.register_output_files(&mut state.output, &c, &atlas_data, &scales) {
+                Ok(()) => {
+                    infoln!(last, "{}", "Already Updated".green());
+                    return;
                 }
+                Err(e) => match e.downcast_ref::<output::Error>().unwrap() {
+                    output::Error::FileExpected => {
+                        infoln!("Output file not found, regenerating it")
+                    }
+                    _ => Err(e).unwrap(),
+                },
             }
         }
 
-        doneln_with_timer!(gathering_graphics_timer);
-
-        let output_atlas_data_path = c
-            .cache
-            .atlas_path()
-            .join(format!("{}.data.json", c.output.name_or_default()));
-
         if c.data.prettify {
             infoln!("Exporting prettified data to file");
         } else {
             infoln!("Exporting data to file");
         }
 
-        traceln!("At {}", output_atlas_data_path.display().to_string().bold());
+        for scale in scales {
+            let mut atlas_data = atlas_data.scaled(scale);
+            atlas_data.meta.textures = self.textures(&c, scale);
+            atlas_data.meta.size = Size::new(
+                state.output.atlas_width * scale,
+                state.output.atlas_height * scale,
+            );
+            atlas_data.meta.scale = scale;
+
+            if let Some(texture) = Self::main_texture(&atlas_data.meta.textures) {
+                atlas_data.meta.image = texture.image.clone();
+                atlas_data.meta.format = Some(texture.format);
+                atlas_data.meta.hash = self.image_hash(&c, texture);
+            }
 
-        // remove file at path
-        if output_atlas_data_path.exists() {
-            fs::remove_file(&output_atlas_data_path).unwrap();
+            let context = ExportContext {
+                name: c.output.scaled_name(scale),
+                image: atlas_data.meta.image.clone(),
+                atlas_size: atlas_data.meta.size.clone(),
+                scale,
+                premultiplied_alpha: c.output.premultiply_alpha,
+                prettify: c.data.prettify,
+                nested_graphics: Self::resolved_graphic_key(&c.data) == GraphicKey::Nested,
+                format_version: Self::resolved_format_version(&c.data),
+            };
 
-            // wait until file is removed, if exists
-            util::wait_until(|| !output_atlas_data_path.exists());
-        }
+            for exporter in self.selected_exporters(&c) {
+                traceln!("Exporting {}", exporter.name().bold());
 
-        if c.data.prettify {
-            atlas_data
-                .save_pretty_to_path(&output_atlas_data_path)
-                .unwrap();
-        } else {
-            atlas_data.save_to_path(&output_atlas_data_path).unwrap();
-        }
+                let mut files = ExportFiles::new(c.cache.atlas_path());
+                exporter.export(&atlas_data, &context, &mut files).unwrap();
 
-        // wait until files are written
-        util::wait_until(|| output_atlas_data_path.exists());
+                for path in files.paths() {
+                    // wait until files are written
+                    util::wait_until(|| path.exists());
 
-        // output
-        let output_file = OutputFile::new(output_atlas_data_path);
-        state.output.register_file(output_file).unwrap();
+                    // output
+                    let output_file = OutputFile::new(path.clone());
+                    state.output.register_file(output_file).unwrap();
+                }
+            }
+        }
 
         doneln_with_timer!(total_timer)
     }
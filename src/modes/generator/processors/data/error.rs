@@ -1,17 +1,39 @@
 use std::{
     error,
     fmt::{self, Debug, Display, Formatter},
+    path::PathBuf,
 };
 
 #[derive(Debug)]
 pub enum Error {
     Save(SaveError),
+    UnknownFormat(String),
+    UnsupportedFormatVersion(u32),
+    InvalidIdentifier(String),
+    IdentifierCollision {
+        identifier: String,
+        names: (String, String),
+    },
+    DuplicateGraphicKey {
+        key: String,
+        locations: (PathBuf, PathBuf),
+    },
+    DuplicateTemplateOutput {
+        suffix: String,
+        templates: (PathBuf, PathBuf),
+    },
 }
 
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match &self {
             Error::Save(save_error) => Some(save_error),
+            Error::UnknownFormat(_)
+            | Error::UnsupportedFormatVersion(_)
+            | Error::InvalidIdentifier(_)
+            | Error::IdentifierCollision { .. }
+            | Error::DuplicateGraphicKey { .. }
+            | Error::DuplicateTemplateOutput { .. } => None,
         }
     }
 }
@@ -22,6 +44,37 @@ impl Display for Error {
             Error::Save(save_error) => {
                 write!(f, "Error when saving an atlas data file: {}", save_error)
             }
+            Error::UnknownFormat(name) => write!(f, "Unknown data format '{}'", name),
+            Error::UnsupportedFormatVersion(version) => write!(
+                f,
+                "Unsupported data format version {}, latest is {}",
+                version,
+                super::MetaData::FORMAT_VERSION
+            ),
+            Error::InvalidIdentifier(name) => write!(
+                f,
+                "Name '{}' has no ASCII letters nor digits to build an identifier from",
+                name
+            ),
+            Error::IdentifierCollision { identifier, names } => write!(
+                f,
+                "Names '{}' and '{}' yields the same identifier '{}'",
+                names.0, names.1, identifier
+            ),
+            Error::DuplicateGraphicKey { key, locations } => write!(
+                f,
+                "Graphic key '{}' is used by both '{}' and '{}', consider changing data.graphic_key",
+                key,
+                locations.0.display(),
+                locations.1.display()
+            ),
+            Error::DuplicateTemplateOutput { suffix, templates } => write!(
+                f,
+                "Templates '{}' and '{}' both output '{{atlas}}.{}', consider renaming one of them",
+                templates.0.display(),
+                templates.1.display(),
+                suffix
+            ),
         }
     }
 }
@@ -29,12 +82,14 @@ impl Display for Error {
 #[derive(Debug)]
 pub enum SaveError {
     Serialize(serde_json::error::Error),
+    MessagePack(rmp_serde::encode::Error),
 }
 
 impl error::Error for SaveError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match &self {
             SaveError::Serialize(json_error) => Some(json_error),
+            SaveError::MessagePack(msgpack_error) => Some(msgpack_error),
         }
     }
 }
@@ -49,6 +104,11 @@ impl Display for SaveError {
                 "Error when serializing into a json file => {}",
                 json_error
             ),
+            SaveError::MessagePack(msgpack_error) => write!(
+                f,
+                "Error when serializing into a msgpack file => {}",
+                msgpack_error
+            ),
         }
     }
 }
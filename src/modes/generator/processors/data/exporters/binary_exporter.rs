@@ -0,0 +1,182 @@
+use std::io::{self, Write};
+
+use crate::graphics::animation::FrameIndices;
+
+use super::{
+    super::{AtlasData, FrameData},
+    DataExporter, ExportContext, ExportFiles,
+};
+
+const MAGIC: &[u8; 4] = b"CLYB";
+
+/// Binary format version, increased at every layout change.
+const BINARY_FORMAT_VERSION: u32 = 1;
+
+/// Fixed layout binary format, every value is a little endian u32.
+/// Layout is described at `docs/atlas data format/binary format.md`.
+pub struct BinaryExporter;
+
+impl BinaryExporter {
+    fn filename(atlas_name: &str) -> String {
+        format!("{}.data.bin", atlas_name)
+    }
+}
+
+impl DataExporter for BinaryExporter {
+    fn name(&self) -> &str {
+        "binary"
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![Self::filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let mut strings = StringTable::default();
+        let image = strings.insert(&context.image);
+
+        let mut graphics = Vec::new();
+        let mut frames = Vec::new();
+        let mut tracks = Vec::new();
+        let mut ranges = Vec::new();
+
+        let names: Vec<&String> = atlas_data.graphics.keys().collect();
+
+        for name in names {
+            let graphic = &atlas_data.graphics[name];
+            let canvas = graphic.canvas_size();
+            let labeled_tracks = graphic.tracks.labeled_entries("/");
+
+            graphics.push([
+                strings.insert(name),
+                canvas.width,
+                canvas.height,
+                frames.len() as u32,
+                graphic.frames.len() as u32,
+                tracks.len() as u32,
+                labeled_tracks.len() as u32,
+            ]);
+
+            for frame in &graphic.frames {
+                frames.push(match frame {
+                    FrameData::Empty => [0; 9],
+                    FrameData::Contents {
+                        atlas_region,
+                        duration,
+                        source_region,
+                        ..
+                    } => [
+                        atlas_region.x,
+                        atlas_region.y,
+                        atlas_region.width,
+                        atlas_region.height,
+                        source_region.x,
+                        source_region.y,
+                        source_region.width,
+                        source_region.height,
+                        duration.unwrap_or(0),
+                    ],
+                });
+            }
+
+            for (label, track) in labeled_tracks {
+                let first_range = ranges.len() as u32;
+
+                for indices in track.indices() {
+                    ranges.push(match indices {
+                        FrameIndices::Value(index) => [*index, *index],
+                        FrameIndices::Range { from, to } => [*from, *to],
+                    });
+                }
+
+                tracks.push([
+                    strings.insert(&label),
+                    first_range,
+                    ranges.len() as u32 - first_range,
+                ]);
+            }
+        }
+
+        let mut w = files.create(&Self::filename(&context.name))?;
+
+        // header
+        w.write_all(MAGIC)?;
+        write_values(
+            &mut w,
+            &[
+                BINARY_FORMAT_VERSION,
+                image,
+                context.atlas_size.width,
+                context.atlas_size.height,
+                context.scale,
+                strings.entries.len() as u32,
+                strings.blob.len() as u32,
+                graphics.len() as u32,
+                frames.len() as u32,
+                tracks.len() as u32,
+                ranges.len() as u32,
+            ],
+        )?;
+
+        // string table
+        for entry in &strings.entries {
+            write_values(&mut w, entry)?;
+        }
+
+        w.write_all(&strings.blob)?;
+
+        // sections
+        for values in &graphics {
+            write_values(&mut w, values)?;
+        }
+
+        for values in &frames {
+            write_values(&mut w, values)?;
+        }
+
+        for values in &tracks {
+            write_values(&mut w, values)?;
+        }
+
+        for values in &ranges {
+            write_values(&mut w, values)?;
+        }
+
+        w.flush()?;
+        Ok(())
+    }
+}
+
+/// Utf-8 strings stored at a single blob, referred by index.
+#[derive(Default)]
+struct StringTable {
+    /// Offset and length of each string at blob.
+    entries: Vec<[u32; 2]>,
+    blob: Vec<u8>,
+}
+
+impl StringTable {
+    fn insert(&mut self, value: &str) -> u32 {
+        self.entries
+            .push([self.blob.len() as u32, value.len() as u32]);
+        self.blob.extend_from_slice(value.as_bytes());
+
+        // keeps next sections 4 bytes aligned
+        self.blob.resize(self.blob.len().div_ceil(4) * 4, 0);
+
+        self.entries.len() as u32 - 1
+    }
+}
+
+fn write_values(w: &mut dyn Write, values: &[u32]) -> io::Result<()> {
+    for value in values {
+        w.write_all(&value.to_le_bytes())?;
+    }
+
+    Ok(())
+}
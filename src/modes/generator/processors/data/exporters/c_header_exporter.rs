@@ -0,0 +1,279 @@
+use std::io::Write;
+
+use crate::graphics::animation::FrameIndices;
+
+use super::{
+    super::{AtlasData, FrameData},
+    identifier, DataExporter, ExportContext, ExportFiles,
+};
+
+/// C header with typed constants, every identifier is prefixed by atlas name.
+pub struct CHeaderExporter;
+
+impl CHeaderExporter {
+    fn filename(atlas_name: &str) -> String {
+        format!("{}.h", atlas_name)
+    }
+}
+
+impl DataExporter for CHeaderExporter {
+    fn name(&self) -> &str {
+        "c-header"
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![Self::filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let names: Vec<&str> = atlas_data.graphics.keys().map(String::as_str).collect();
+
+        let prefix = identifier::snake_case(&context.name)?;
+        let upper_prefix = identifier::upper_snake_case(&context.name)?;
+        let graphics = identifier::unique(&names, identifier::snake_case)?;
+
+        // every symbol shares a single namespace, paired with the name it comes from
+        let mut symbols: Vec<(String, String)> = Vec::new();
+        let atlas_symbol =
+            |suffix: &str| (context.name.clone(), format!("{}{}", upper_prefix, suffix));
+        symbols.extend(
+            [
+                "_H",
+                "_IMAGE",
+                "_WIDTH",
+                "_HEIGHT",
+                "_SCALE",
+                "_GRAPHIC_COUNT",
+            ]
+            .map(atlas_symbol),
+        );
+        symbols.push((context.name.clone(), format!("{}_graphic", prefix)));
+
+        // header is only written once every symbol is known to be unique
+        let mut w: Vec<u8> = Vec::new();
+
+        writeln!(w, "/* Generated by clymene, do not edit. */")?;
+        writeln!(w)?;
+        writeln!(w, "#ifndef {}_H", upper_prefix)?;
+        writeln!(w, "#define {}_H", upper_prefix)?;
+        writeln!(w)?;
+        writeln!(w, "#include <stdint.h>")?;
+        writeln!(w)?;
+
+        // shared between every generated header
+        writeln!(w, "#ifndef CLYMENE_TYPES")?;
+        writeln!(w, "#define CLYMENE_TYPES")?;
+        writeln!(w)?;
+        writeln!(w, "typedef struct {{")?;
+        writeln!(w, "    uint32_t x, y, width, height;")?;
+        writeln!(w, "}} clymene_rect;")?;
+        writeln!(w)?;
+        writeln!(w, "/* empty frames have every value as zero */")?;
+        writeln!(w, "typedef struct {{")?;
+        writeln!(w, "    clymene_rect atlas;")?;
+        writeln!(w, "    clymene_rect source;")?;
+        writeln!(
+            w,
+            "    uint32_t duration; /* milliseconds, zero when undefined */"
+        )?;
+        writeln!(w, "}} clymene_frame;")?;
+        writeln!(w)?;
+        writeln!(w, "/* frame indices, both inclusive */")?;
+        writeln!(w, "typedef struct {{")?;
+        writeln!(w, "    uint32_t from, to;")?;
+        writeln!(w, "}} clymene_range;")?;
+        writeln!(w)?;
+        writeln!(w, "#endif")?;
+        writeln!(w)?;
+        writeln!(
+            w,
+            "#define {}_IMAGE {}",
+            upper_prefix,
+            c_string(&context.image)
+        )?;
+        writeln!(
+            w,
+            "#define {}_WIDTH {}",
+            upper_prefix, context.atlas_size.width
+        )?;
+        writeln!(
+            w,
+            "#define {}_HEIGHT {}",
+            upper_prefix, context.atlas_size.height
+        )?;
+        writeln!(w, "#define {}_SCALE {}", upper_prefix, context.scale)?;
+        writeln!(w)?;
+
+        writeln!(w, "enum {}_graphic {{", prefix)?;
+
+        for (name, graphic) in &graphics {
+            let symbol = format!("{}_{}", upper_prefix, graphic.to_ascii_uppercase());
+            writeln!(w, "    {},", symbol)?;
+            symbols.push((name.to_string(), symbol));
+        }
+
+        writeln!(w, "    {}_GRAPHIC_COUNT", upper_prefix)?;
+        writeln!(w, "}};")?;
+
+        for (name, graphic) in &graphics {
+            let graphic_data = &atlas_data.graphics[*name];
+            let canvas = graphic_data.canvas_size();
+            let upper_graphic = format!("{}_{}", upper_prefix, graphic.to_ascii_uppercase());
+            let lower_graphic = format!("{}_{}", prefix, graphic);
+
+            symbols.extend(
+                ["_CANVAS_WIDTH", "_CANVAS_HEIGHT", "_FRAME_COUNT"]
+                    .map(|suffix| (name.to_string(), format!("{}{}", upper_graphic, suffix))),
+            );
+
+            writeln!(w)?;
+            writeln!(w, "/* {} */", name.replace("*/", "* /"))?;
+            writeln!(w, "#define {}_CANVAS_WIDTH {}", upper_graphic, canvas.width)?;
+            writeln!(
+                w,
+                "#define {}_CANVAS_HEIGHT {}",
+                upper_graphic, canvas.height
+            )?;
+            writeln!(
+                w,
+                "#define {}_FRAME_COUNT {}",
+                upper_graphic,
+                graphic_data.frames.len()
+            )?;
+
+            if !graphic_data.frames.is_empty() {
+                let symbol = format!("{}_frames", lower_graphic);
+                writeln!(
+                    w,
+                    "static const clymene_frame {}[{}] = {{",
+                    symbol,
+                    graphic_data.frames.len()
+                )?;
+                symbols.push((name.to_string(), symbol));
+
+                for frame in &graphic_data.frames {
+                    match frame {
+                        FrameData::Empty => {
+                            writeln!(w, "    {{ {{ 0, 0, 0, 0 }}, {{ 0, 0, 0, 0 }}, 0 }},")?
+                        }
+                        FrameData::Contents {
+                            atlas_region,
+                            duration,
+                            source_region,
+                            ..
+                        } => writeln!(
+                            w,
+                            "    {{ {{ {}, {}, {}, {} }}, {{ {}, {}, {}, {} }}, {} }},",
+                            atlas_region.x,
+                            atlas_region.y,
+                            atlas_region.width,
+                            atlas_region.height,
+                            source_region.x,
+                            source_region.y,
+                            source_region.width,
+                            source_region.height,
+                            duration.unwrap_or(0)
+                        )?,
+                    }
+                }
+
+                writeln!(w, "}};")?;
+            }
+
+            let labeled_tracks = graphic_data.tracks.labeled_entries("/");
+            let labels: Vec<&str> = labeled_tracks.iter().map(|(l, _)| l.as_str()).collect();
+            let tracks = identifier::unique(&labels, identifier::upper_snake_case)?;
+
+            for ((label, track), (_, track_name)) in labeled_tracks.iter().zip(tracks) {
+                let ranges: Vec<String> = track
+                    .indices()
+                    .into_iter()
+                    .map(|indices| match indices {
+                        FrameIndices::Value(i) => format!("{{ {}, {} }}", i, i),
+                        FrameIndices::Range { from, to } => format!("{{ {}, {} }}", from, to),
+                    })
+                    .collect();
+
+                // tracks have their own infix, so they can't be taken by frames
+                let origin = format!("{}/{}", name, label);
+                let count_symbol = format!("{}_TRACK_{}_RANGE_COUNT", upper_graphic, track_name);
+                let symbol = format!(
+                    "{}_track_{}",
+                    lower_graphic,
+                    track_name.to_ascii_lowercase()
+                );
+
+                writeln!(w, "#define {} {}", count_symbol, ranges.len())?;
+                writeln!(
+                    w,
+                    "static const clymene_range {}[{}] = {{ {} }};",
+                    symbol,
+                    ranges.len(),
+                    ranges.join(", ")
+                )?;
+
+                symbols.push((origin.clone(), count_symbol));
+                symbols.push((origin, symbol));
+            }
+        }
+
+        writeln!(w)?;
+        writeln!(w, "#endif")?;
+
+        identifier::ensure_unique(&symbols)?;
+
+        let mut file = files.create(&Self::filename(&context.name))?;
+        file.write_all(&w)?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// C string literal, every byte outside printable ASCII is written as an hex escape.
+fn c_string(value: &str) -> String {
+    let mut literal = String::from("\"");
+    let mut after_hex_escape = false;
+
+    for byte in value.bytes() {
+        match byte {
+            b'\\' | b'"' => {
+                literal.push('\\');
+                literal.push(byte as char);
+            }
+            // an hex escape would take following hex digits as well, so literal is split
+            b if after_hex_escape && b.is_ascii_hexdigit() => {
+                literal.push_str("\"\"");
+                literal.push(b as char);
+            }
+            0x20..=0x7E => literal.push(byte as char),
+            _ => {
+                literal.push_str(&format!("\\x{:02X}", byte));
+                after_hex_escape = true;
+                continue;
+            }
+        }
+
+        after_hex_escape = false;
+    }
+
+    literal.push('"');
+    literal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn c_string_escaping() {
+        assert_eq!(c_string("atlas.png"), r#""atlas.png""#);
+        assert_eq!(c_string(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(c_string("\u{e9}t\u{e9}.png"), r#""\xC3\xA9t\xC3\xA9.png""#);
+        assert_eq!(c_string("\u{e9}a\n"), r#""\xC3\xA9""a\x0A""#);
+    }
+}
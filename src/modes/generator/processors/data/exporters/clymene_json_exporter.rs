@@ -0,0 +1,53 @@
+use std::io::Write;
+
+use super::{
+    super::{AtlasData, MetaData, SaveError},
+    DataExporter, ExportContext, ExportFiles,
+};
+
+/// Clymene's own json format.
+pub struct ClymeneJsonExporter;
+
+impl ClymeneJsonExporter {
+    fn filename(atlas_name: &str) -> String {
+        format!("{}.data.json", atlas_name)
+    }
+}
+
+impl DataExporter for ClymeneJsonExporter {
+    fn name(&self) -> &str {
+        "clymene-json"
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![Self::filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let mut writer = files.create(&Self::filename(&context.name))?;
+
+        if context.nested_graphics || context.format_version != MetaData::FORMAT_VERSION {
+            let versioned =
+                atlas_data.versioned(context.format_version, context.nested_graphics)?;
+
+            if context.prettify {
+                serde_json::to_writer_pretty(&mut writer, &versioned)
+                    .map_err(SaveError::Serialize)?;
+            } else {
+                serde_json::to_writer(&mut writer, &versioned).map_err(SaveError::Serialize)?;
+            }
+
+            writer.flush()?;
+            Ok(())
+        } else if context.prettify {
+            atlas_data.save_pretty(writer)
+        } else {
+            atlas_data.save(writer)
+        }
+    }
+}
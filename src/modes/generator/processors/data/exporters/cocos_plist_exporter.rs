@@ -0,0 +1,153 @@
+use std::io::Write;
+
+use super::{
+    super::{AtlasData, FrameData},
+    xml, DataExporter, ExportContext, ExportFiles,
+};
+
+/// Cocos2d-x sprite sheet plist, using format 3.
+pub struct CocosPlistExporter;
+
+impl CocosPlistExporter {
+    fn filename(atlas_name: &str) -> String {
+        format!("{}.plist", atlas_name)
+    }
+}
+
+impl DataExporter for CocosPlistExporter {
+    fn name(&self) -> &str {
+        "cocos-plist"
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![Self::filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let mut w = files.create(&Self::filename(&context.name))?;
+
+        writeln!(w, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(w, "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">")?;
+        writeln!(w, "<plist version=\"1.0\">")?;
+        writeln!(w, "    <dict>")?;
+        writeln!(w, "        <key>frames</key>")?;
+        writeln!(w, "        <dict>")?;
+
+        let names: Vec<&String> = atlas_data.graphics.keys().collect();
+
+        for name in names {
+            let graphic = &atlas_data.graphics[name];
+            let canvas = graphic.canvas_size();
+
+            for (index, frame) in graphic.frames.iter().enumerate() {
+                if let FrameData::Contents {
+                    atlas_region,
+                    source_region,
+                    pivot,
+                    ..
+                } = frame
+                {
+                    // offset from canvas center to trimmed region center, y axis points up
+                    let offset_x = (2 * source_region.x as i64 + source_region.width as i64
+                        - canvas.width as i64) as f32
+                        / 2.0;
+                    let offset_y = (canvas.height as i64
+                        - 2 * source_region.y as i64
+                        - source_region.height as i64) as f32
+                        / 2.0;
+
+                    writeln!(w, "            <key>{}/{}</key>", xml::escape(name), index)?;
+                    writeln!(w, "            <dict>")?;
+                    writeln!(w, "                <key>aliases</key>")?;
+                    writeln!(w, "                <array/>")?;
+
+                    if let Some(pivot) = pivot {
+                        // normalized by source size, y axis points up
+                        writeln!(w, "                <key>anchor</key>")?;
+                        writeln!(
+                            w,
+                            "                <string>{{{},{}}}</string>",
+                            pivot.canvas.x as f32 / canvas.width.max(1) as f32,
+                            1.0 - pivot.canvas.y as f32 / canvas.height.max(1) as f32
+                        )?;
+                    }
+
+                    writeln!(w, "                <key>spriteOffset</key>")?;
+                    writeln!(
+                        w,
+                        "                <string>{{{},{}}}</string>",
+                        offset_x, offset_y
+                    )?;
+                    writeln!(w, "                <key>spriteSize</key>")?;
+                    writeln!(
+                        w,
+                        "                <string>{{{},{}}}</string>",
+                        atlas_region.width, atlas_region.height
+                    )?;
+                    writeln!(w, "                <key>spriteSourceSize</key>")?;
+                    writeln!(
+                        w,
+                        "                <string>{{{},{}}}</string>",
+                        canvas.width, canvas.height
+                    )?;
+                    writeln!(w, "                <key>textureRect</key>")?;
+                    writeln!(
+                        w,
+                        "                <string>{{{{{},{}}},{{{},{}}}}}</string>",
+                        atlas_region.x, atlas_region.y, atlas_region.width, atlas_region.height
+                    )?;
+                    writeln!(w, "                <key>textureRotated</key>")?;
+                    writeln!(w, "                <false/>")?;
+                    writeln!(w, "            </dict>")?;
+                }
+            }
+        }
+
+        writeln!(w, "        </dict>")?;
+        writeln!(w, "        <key>metadata</key>")?;
+        writeln!(w, "        <dict>")?;
+        writeln!(w, "            <key>format</key>")?;
+        writeln!(w, "            <integer>3</integer>")?;
+        writeln!(w, "            <key>pixelFormat</key>")?;
+        writeln!(w, "            <string>RGBA8888</string>")?;
+        writeln!(w, "            <key>premultiplyAlpha</key>")?;
+        writeln!(
+            w,
+            "            <{}/>",
+            if context.premultiplied_alpha {
+                "true"
+            } else {
+                "false"
+            }
+        )?;
+        writeln!(w, "            <key>realTextureFileName</key>")?;
+        writeln!(
+            w,
+            "            <string>{}</string>",
+            xml::escape(&context.image)
+        )?;
+        writeln!(w, "            <key>size</key>")?;
+        writeln!(
+            w,
+            "            <string>{{{},{}}}</string>",
+            context.atlas_size.width, context.atlas_size.height
+        )?;
+        writeln!(w, "            <key>textureFileName</key>")?;
+        writeln!(
+            w,
+            "            <string>{}</string>",
+            xml::escape(&context.image)
+        )?;
+        writeln!(w, "        </dict>")?;
+        writeln!(w, "    </dict>")?;
+        writeln!(w, "</plist>")?;
+        w.flush()?;
+
+        Ok(())
+    }
+}
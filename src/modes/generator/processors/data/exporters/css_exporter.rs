@@ -0,0 +1,207 @@
+use std::io::Write;
+
+use crate::math::{Rectangle, Size};
+
+use super::{
+    super::{AtlasData, FrameData},
+    DataExporter, ExportContext, ExportFiles,
+};
+
+/// Css sprite sheet, a class per graphic and per frame, and keyframes per track.
+pub struct CssExporter;
+
+impl CssExporter {
+    fn filename(atlas_name: &str) -> String {
+        format!("{}.css", atlas_name)
+    }
+
+    /// Declarations which displays a frame, keeping canvas footprint through margins.
+    fn frame_declarations(
+        atlas_region: &Rectangle<u32>,
+        source_region: &Rectangle<u32>,
+        canvas: &Size<u32>,
+    ) -> String {
+        format!(
+            "width: {}px; height: {}px; background-position: -{}px -{}px; margin: {}px {}px {}px {}px;",
+            atlas_region.width,
+            atlas_region.height,
+            atlas_region.x,
+            atlas_region.y,
+            source_region.y,
+            canvas
+                .width
+                .saturating_sub(source_region.x + source_region.width),
+            canvas
+                .height
+                .saturating_sub(source_region.y + source_region.height),
+            source_region.x
+        )
+    }
+}
+
+impl DataExporter for CssExporter {
+    fn name(&self) -> &str {
+        "css"
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![Self::filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let prefix = class_name(&context.name);
+        let mut w = files.create(&Self::filename(&context.name))?;
+
+        writeln!(w, "/* Generated by clymene, do not edit. */")?;
+        writeln!(w)?;
+        writeln!(w, ".{} {{", prefix)?;
+        writeln!(w, "    display: inline-block;")?;
+        writeln!(
+            w,
+            "    background-image: url(\"{}\");",
+            css_string(&context.image)
+        )?;
+        writeln!(w, "    background-repeat: no-repeat;")?;
+        writeln!(w, "}}")?;
+
+        let names: Vec<&String> = atlas_data.graphics.keys().collect();
+
+        for name in names {
+            let graphic = &atlas_data.graphics[name];
+            let canvas = graphic.canvas_size();
+            let graphic_class = format!("{}-{}", prefix, class_name(name));
+
+            // graphic class displays it's first frame
+            let first_frame = graphic.frames.iter().find_map(|frame| match frame {
+                FrameData::Contents {
+                    atlas_region,
+                    source_region,
+                    ..
+                } => Some(Self::frame_declarations(
+                    atlas_region,
+                    source_region,
+                    &canvas,
+                )),
+                FrameData::Empty => None,
+            });
+
+            if let Some(declarations) = first_frame {
+                writeln!(w)?;
+                writeln!(w, ".{} {{ {} }}", graphic_class, declarations)?;
+            }
+
+            for (index, frame) in graphic.frames.iter().enumerate() {
+                if let FrameData::Contents {
+                    atlas_region,
+                    source_region,
+                    ..
+                } = frame
+                {
+                    writeln!(
+                        w,
+                        ".{}-{} {{ {} }}",
+                        graphic_class,
+                        index,
+                        Self::frame_declarations(atlas_region, source_region, &canvas)
+                    )?;
+                }
+            }
+
+            for (label, track) in graphic.tracks.labeled_entries("/") {
+                let frames: Vec<(&FrameData, u32)> = track
+                    .indices()
+                    .frames()
+                    .filter_map(|i| graphic.frames.get(i as usize))
+                    .map(|frame| {
+                        let duration = match frame {
+                            FrameData::Contents {
+                                duration: Some(duration),
+                                ..
+                            } => *duration,
+                            _ => 0,
+                        };
+
+                        (frame, duration)
+                    })
+                    .collect();
+
+                let total_duration: u32 = frames.iter().map(|(_, d)| d).sum();
+
+                if total_duration == 0 {
+                    continue;
+                }
+
+                let animation_name = format!("{}-{}", graphic_class, class_name(&label));
+
+                writeln!(w)?;
+                writeln!(w, "@keyframes {} {{", animation_name)?;
+
+                let mut elapsed = 0;
+                let mut last_declarations = String::new();
+
+                for (frame, duration) in &frames {
+                    // empty frames keeps previous region, but hidden
+                    last_declarations = match frame {
+                        FrameData::Contents {
+                            atlas_region,
+                            source_region,
+                            ..
+                        } => format!(
+                            "{} opacity: 1;",
+                            Self::frame_declarations(atlas_region, source_region, &canvas)
+                        ),
+                        FrameData::Empty => "opacity: 0;".to_owned(),
+                    };
+
+                    writeln!(
+                        w,
+                        "    {}% {{ {} }}",
+                        percentage(elapsed, total_duration),
+                        last_declarations
+                    )?;
+
+                    elapsed += duration;
+                }
+
+                writeln!(w, "    100% {{ {} }}", last_declarations)?;
+                writeln!(w, "}}")?;
+                writeln!(
+                    w,
+                    ".{} {{ animation: {} {}ms step-end infinite; }}",
+                    animation_name, animation_name, total_duration
+                )?;
+            }
+        }
+
+        w.flush()?;
+        Ok(())
+    }
+}
+
+/// Keeps only characters valid at css class names.
+fn class_name(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn css_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn percentage(value: u32, total: u32) -> String {
+    let p = (value as f64 / total as f64) * 100.0;
+    format!("{}", (p * 1000.0).round() / 1000.0)
+}
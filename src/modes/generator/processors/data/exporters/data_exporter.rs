@@ -0,0 +1,17 @@
+use super::{super::AtlasData, ExportContext, ExportFiles};
+
+/// Writes atlas data using a specific file format.
+pub trait DataExporter {
+    /// Format identifier, as expected at `data.formats`.
+    fn name(&self) -> &str;
+
+    /// Every filename it writes, from atlas name and atlas data.
+    fn filenames(&self, atlas_name: &str, atlas_data: &AtlasData) -> Vec<String>;
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()>;
+}
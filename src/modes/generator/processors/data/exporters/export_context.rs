@@ -0,0 +1,23 @@
+use crate::math::Size;
+
+/// Atlas details which data doesn't holds by itself.
+pub struct ExportContext {
+    /// Atlas name, already scaled.
+    pub name: String,
+
+    /// Main atlas image filename.
+    pub image: String,
+
+    /// Atlas size, already scaled.
+    pub atlas_size: Size<u32>,
+
+    pub scale: u32,
+    pub premultiplied_alpha: bool,
+    pub prettify: bool,
+
+    /// Graphics should be nested following their key path, when format supports it.
+    pub nested_graphics: bool,
+
+    /// Data format version to be emitted, when format supports it.
+    pub format_version: u32,
+}
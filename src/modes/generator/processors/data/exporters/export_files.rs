@@ -0,0 +1,47 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+
+use crate::util;
+
+/// Files created by an exporter, every one at the same directory.
+pub struct ExportFiles {
+    directory: PathBuf,
+    paths: Vec<PathBuf>,
+}
+
+impl ExportFiles {
+    pub fn new<P: AsRef<Path>>(directory: P) -> Self {
+        Self {
+            directory: directory.as_ref().to_owned(),
+            paths: Vec::new(),
+        }
+    }
+
+    /// Creates a file to be written, replacing it if already exists.
+    pub fn create(&mut self, filename: &str) -> eyre::Result<BufWriter<File>> {
+        let path = self.directory.join(filename);
+        traceln!("At {}", path.display().to_string().bold());
+
+        // remove file at path
+        if path.exists() {
+            fs::remove_file(&path)?;
+
+            // wait until file is removed, if exists
+            util::wait_until(|| !path.exists());
+        }
+
+        let file = File::create(&path)?;
+        self.paths.push(path);
+
+        Ok(BufWriter::new(file))
+    }
+
+    pub fn paths(&self) -> &Vec<PathBuf> {
+        &self.paths
+    }
+}
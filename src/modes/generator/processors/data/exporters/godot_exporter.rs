@@ -0,0 +1,366 @@
+use std::{collections::HashMap, io::Write, path::PathBuf};
+
+use crate::math::{Rectangle, Size};
+
+use super::{
+    super::{AtlasData, Error, FrameData, GraphicData},
+    DataExporter, ExportContext, ExportFiles,
+};
+
+/// Frame duration used when it isn't defined, in milliseconds.
+const DEFAULT_FRAME_DURATION: u32 = 100;
+
+/// Godot 4 text resources, an `AtlasTexture` for every single image
+/// and a `SpriteFrames` for every animation.
+pub struct GodotExporter;
+
+impl GodotExporter {
+    fn filename(atlas_name: &str, graphic_name: &str) -> String {
+        format!("{}.{}.tres", atlas_name, graphic_name.replace('/', "_"))
+    }
+
+    fn is_animation(graphic: &GraphicData) -> bool {
+        graphic.frames.len() > 1 || !graphic.tracks.is_empty()
+    }
+
+    /// Graphics without any frame contents don't yield a resource,
+    /// an empty region would make Godot display whole atlas instead.
+    fn is_exported(graphic: &GraphicData) -> bool {
+        graphic
+            .frames
+            .iter()
+            .any(|frame| matches!(frame, FrameData::Contents { .. }))
+    }
+
+    fn write_atlas_texture(
+        w: &mut dyn Write,
+        atlas_region: &Rectangle<u32>,
+        source_region: &Rectangle<u32>,
+        canvas: &Size<u32>,
+    ) -> eyre::Result<()> {
+        writeln!(w, "atlas = ExtResource(\"1_atlas\")")?;
+        writeln!(w, "region = {}", rect2(atlas_region))?;
+
+        // margin holds where trimmed region is at canvas
+        // and how much it should grow to match canvas size
+        writeln!(
+            w,
+            "margin = {}",
+            rect2(&Rectangle::new(
+                source_region.x,
+                source_region.y,
+                canvas.width.saturating_sub(source_region.width),
+                canvas.height.saturating_sub(source_region.height),
+            ))
+        )?;
+
+        Ok(())
+    }
+
+    fn write_sprite_frames(
+        w: &mut dyn Write,
+        graphic: &GraphicData,
+        canvas: &Size<u32>,
+    ) -> eyre::Result<()> {
+        // empty frames have no texture, so they only take their time
+        for (index, frame) in graphic.frames.iter().enumerate() {
+            if let FrameData::Contents {
+                atlas_region,
+                source_region,
+                ..
+            } = frame
+            {
+                writeln!(w)?;
+                writeln!(
+                    w,
+                    "[sub_resource type=\"AtlasTexture\" id=\"AtlasTexture_{}\"]",
+                    index
+                )?;
+                Self::write_atlas_texture(w, atlas_region, source_region, canvas)?;
+            }
+        }
+
+        // every track becomes an animation, or a single one with every frame
+        let all_frames: Vec<u32> = (0..graphic.frames.len() as u32).collect();
+        let labeled_tracks = graphic.tracks.labeled_entries("/");
+        let animations: Vec<(String, Vec<u32>)> = if labeled_tracks.is_empty() {
+            vec![("default".to_owned(), all_frames)]
+        } else {
+            labeled_tracks
+                .into_iter()
+                .map(|(label, track)| (label, track.indices().frames().collect()))
+                .collect()
+        };
+
+        writeln!(w)?;
+        writeln!(w, "[resource]")?;
+        write!(w, "animations = [")?;
+
+        for (animation_index, (label, indices)) in animations.iter().enumerate() {
+            let durations: Vec<(u32, u32)> = indices
+                .iter()
+                .filter(|i| (**i as usize) < graphic.frames.len())
+                .map(|i| {
+                    let duration = match &graphic.frames[*i as usize] {
+                        FrameData::Contents {
+                            duration: Some(duration),
+                            ..
+                        } if *duration > 0 => *duration,
+                        _ => DEFAULT_FRAME_DURATION,
+                    };
+
+                    (*i, duration)
+                })
+                .collect();
+
+            // speed is set to shortest frame, every frame duration is relative to it
+            let base_duration = durations
+                .iter()
+                .map(|(_, d)| *d)
+                .min()
+                .unwrap_or(DEFAULT_FRAME_DURATION);
+
+            if animation_index > 0 {
+                write!(w, ", ")?;
+            }
+
+            writeln!(w, "{{")?;
+            write!(w, "\"frames\": [")?;
+
+            for (i, (frame_index, duration)) in durations.iter().enumerate() {
+                if i > 0 {
+                    write!(w, ", ")?;
+                }
+
+                writeln!(w, "{{")?;
+                writeln!(
+                    w,
+                    "\"duration\": {:?},",
+                    *duration as f64 / base_duration as f64
+                )?;
+                match &graphic.frames[*frame_index as usize] {
+                    FrameData::Contents { .. } => writeln!(
+                        w,
+                        "\"texture\": SubResource(\"AtlasTexture_{}\")",
+                        frame_index
+                    )?,
+                    FrameData::Empty => writeln!(w, "\"texture\": null")?,
+                }
+                write!(w, "}}")?;
+            }
+
+            writeln!(w, "],")?;
+            writeln!(w, "\"loop\": true,")?;
+            writeln!(w, "\"name\": &\"{}\",", escape(label))?;
+            writeln!(w, "\"speed\": {:?}", 1000.0 / base_duration as f64)?;
+            write!(w, "}}")?;
+        }
+
+        writeln!(w, "]")?;
+
+        Ok(())
+    }
+}
+
+impl DataExporter for GodotExporter {
+    fn name(&self) -> &str {
+        "godot"
+    }
+
+    fn filenames(&self, atlas_name: &str, atlas_data: &AtlasData) -> Vec<String> {
+        atlas_data
+            .graphics
+            .iter()
+            .filter(|(_, graphic)| Self::is_exported(graphic))
+            .map(|(graphic_name, _)| Self::filename(atlas_name, graphic_name))
+            .collect()
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let names: Vec<&String> = atlas_data.graphics.keys().collect();
+
+        // path separators are flattened, so different keys may share a resource file
+        let mut filenames: HashMap<String, &String> = HashMap::with_capacity(names.len());
+
+        for name in &names {
+            let filename = Self::filename(&context.name, name);
+
+            if let Some(other) = filenames.insert(filename.clone(), name) {
+                return Err(Error::DuplicateGraphicKey {
+                    key: filename,
+                    locations: (PathBuf::from(other), PathBuf::from(name)),
+                }
+                .into());
+            }
+        }
+
+        for name in names {
+            let graphic = &atlas_data.graphics[name];
+
+            if !Self::is_exported(graphic) {
+                continue;
+            }
+
+            let canvas = graphic.canvas_size();
+            let mut w = files.create(&Self::filename(&context.name, name))?;
+
+            if Self::is_animation(graphic) {
+                let texture_count = graphic
+                    .frames
+                    .iter()
+                    .filter(|frame| matches!(frame, FrameData::Contents { .. }))
+                    .count();
+
+                writeln!(
+                    w,
+                    "[gd_resource type=\"SpriteFrames\" load_steps={} format=3]",
+                    texture_count + 2
+                )?;
+            } else {
+                writeln!(
+                    w,
+                    "[gd_resource type=\"AtlasTexture\" load_steps=2 format=3]"
+                )?;
+            }
+
+            // atlas image is at the same directory as resource file
+            writeln!(w)?;
+            writeln!(
+                w,
+                "[ext_resource type=\"Texture2D\" path=\"{}\" id=\"1_atlas\"]",
+                escape(&context.image)
+            )?;
+
+            if Self::is_animation(graphic) {
+                Self::write_sprite_frames(&mut w, graphic, &canvas)?;
+            } else {
+                writeln!(w)?;
+                writeln!(w, "[resource]")?;
+
+                // single frame has contents, otherwise it wouldn't be exported
+                if let Some(FrameData::Contents {
+                    atlas_region,
+                    source_region,
+                    ..
+                }) = graphic.frames.first()
+                {
+                    Self::write_atlas_texture(&mut w, atlas_region, source_region, &canvas)?;
+                }
+            }
+
+            w.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+fn rect2(rect: &Rectangle<u32>) -> String {
+    format!(
+        "Rect2({}, {}, {}, {})",
+        rect.x, rect.y, rect.width, rect.height
+    )
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+    use crate::graphics::animation::{FrameIndicesGroup, Track};
+
+    fn contents(x: u32) -> FrameData {
+        FrameData::Contents {
+            atlas_region: Rectangle::new(x, 0, 4, 4),
+            duration: Some(100),
+            source_region: Rectangle::new(0, 0, 4, 4),
+            pivot: None,
+            hitboxes: Default::default(),
+        }
+    }
+
+    fn graphic(frames: Vec<FrameData>) -> GraphicData {
+        let mut graphic = GraphicData::new();
+        graphic.frames = frames;
+        graphic.canvas = Some(Size::new(4, 4));
+        graphic
+    }
+
+    #[test]
+    fn empty_frames_have_no_texture() {
+        let directory = env::temp_dir().join("clymene_godot_exporter");
+        fs::create_dir_all(&directory).unwrap();
+
+        let mut walk = graphic(vec![contents(0), FrameData::Empty, contents(4)]);
+        walk.tracks.register(Track::new(
+            Some("walk".to_owned()),
+            FrameIndicesGroup::with_range(0, 2),
+        ));
+
+        let mut atlas_data = AtlasData::new();
+        atlas_data.graphics.insert("walk".to_owned(), walk);
+        atlas_data
+            .graphics
+            .insert("blank".to_owned(), graphic(vec![FrameData::Empty]));
+
+        let context = ExportContext {
+            name: "atlas".to_owned(),
+            image: "atlas.png".to_owned(),
+            atlas_size: Size::new(8, 4),
+            scale: 1,
+            premultiplied_alpha: false,
+            prettify: false,
+            nested_graphics: false,
+            format_version: 0,
+        };
+
+        let mut files = ExportFiles::new(&directory);
+        GodotExporter
+            .export(&atlas_data, &context, &mut files)
+            .unwrap();
+
+        // entirely empty graphic isn't exported
+        assert_eq!(
+            GodotExporter.filenames("atlas", &atlas_data),
+            vec!["atlas.walk.tres".to_owned()]
+        );
+        assert_eq!(files.paths(), &vec![directory.join("atlas.walk.tres")]);
+
+        let resource = fs::read_to_string(directory.join("atlas.walk.tres")).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(resource.starts_with("[gd_resource type=\"SpriteFrames\" load_steps=4 format=3]"));
+        assert_eq!(
+            resource
+                .matches("[sub_resource type=\"AtlasTexture\"")
+                .count(),
+            2
+        );
+        assert!(!resource.contains("AtlasTexture_1"));
+        assert!(!resource.contains("region = Rect2(0, 0, 0, 0)"));
+
+        // empty frame still takes it's time slot
+        let textures: Vec<&str> = resource
+            .lines()
+            .filter(|line| line.starts_with("\"texture\""))
+            .collect();
+
+        assert_eq!(
+            textures,
+            vec![
+                "\"texture\": SubResource(\"AtlasTexture_0\")",
+                "\"texture\": null",
+                "\"texture\": SubResource(\"AtlasTexture_2\")",
+            ]
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::Write;
+
+use super::{
+    super::{AtlasData, FrameData},
+    xml, DataExporter, ExportContext, ExportFiles,
+};
+
+/// Standalone html page which displays atlas image, highlighting every region on hover.
+pub struct HtmlPreviewExporter;
+
+impl HtmlPreviewExporter {
+    fn filename(atlas_name: &str) -> String {
+        format!("{}.preview.html", atlas_name)
+    }
+}
+
+impl DataExporter for HtmlPreviewExporter {
+    fn name(&self) -> &str {
+        "html-preview"
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![Self::filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let mut w = files.create(&Self::filename(&context.name))?;
+        let title = xml::escape(&context.name);
+
+        writeln!(w, "<!DOCTYPE html>")?;
+        writeln!(w, "<html>")?;
+        writeln!(w, "<head>")?;
+        writeln!(w, "<meta charset=\"utf-8\">")?;
+        writeln!(w, "<title>{}</title>", title)?;
+        writeln!(w, "<style>")?;
+        writeln!(
+            w,
+            "body {{ background: #222; color: #ddd; font-family: sans-serif; }}"
+        )?;
+        writeln!(w, ".atlas {{ position: relative; display: inline-block; background: repeating-conic-gradient(#555 0% 25%, #444 0% 50%) 0 0 / 16px 16px; }}")?;
+        writeln!(
+            w,
+            ".atlas img {{ display: block; image-rendering: pixelated; }}"
+        )?;
+        writeln!(w, ".region {{ position: absolute; box-sizing: border-box; outline: 1px solid rgba(255, 255, 255, 0.2); }}")?;
+        writeln!(w, ".region:hover {{ outline: 1px solid #ff0; background: rgba(255, 255, 0, 0.3); z-index: 1; }}")?;
+        writeln!(w, "</style>")?;
+        writeln!(w, "</head>")?;
+        writeln!(w, "<body>")?;
+        writeln!(w, "<h1>{}</h1>", title)?;
+        writeln!(
+            w,
+            "<p>{}x{}, scale {}, {} graphics</p>",
+            context.atlas_size.width,
+            context.atlas_size.height,
+            context.scale,
+            atlas_data.graphics.len()
+        )?;
+        writeln!(w, "<div class=\"atlas\">")?;
+        writeln!(
+            w,
+            "<img src=\"{}\" width=\"{}\" height=\"{}\">",
+            xml::escape(&context.image),
+            context.atlas_size.width,
+            context.atlas_size.height
+        )?;
+
+        let names: Vec<&String> = atlas_data.graphics.keys().collect();
+
+        for name in names {
+            for (index, frame) in atlas_data.graphics[name].frames.iter().enumerate() {
+                if let FrameData::Contents {
+                    atlas_region,
+                    duration,
+                    ..
+                } = frame
+                {
+                    let duration = match duration {
+                        Some(duration) => format!(", {}ms", duration),
+                        None => String::new(),
+                    };
+
+                    writeln!(
+                        w,
+                        "<div class=\"region\" style=\"left: {}px; top: {}px; width: {}px; height: {}px;\" title=\"{}/{} ({}, {}, {}x{}{})\"></div>",
+                        atlas_region.x,
+                        atlas_region.y,
+                        atlas_region.width,
+                        atlas_region.height,
+                        xml::escape(name),
+                        index,
+                        atlas_region.x,
+                        atlas_region.y,
+                        atlas_region.width,
+                        atlas_region.height,
+                        duration
+                    )?;
+                }
+            }
+        }
+
+        writeln!(w, "</div>")?;
+        writeln!(w, "</body>")?;
+        writeln!(w, "</html>")?;
+
+        w.flush()?;
+        Ok(())
+    }
+}
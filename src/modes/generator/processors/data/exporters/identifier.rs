@@ -0,0 +1,152 @@
+//! Conversions from graphic names and track labels into source code identifiers.
+
+use std::collections::HashMap;
+
+use super::super::Error;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+fn words(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+}
+
+/// Identifiers can't start with a digit nor be empty.
+fn valid(name: &str, identifier: String) -> Result<String, Error> {
+    match identifier.chars().next() {
+        Some(c) if c.is_ascii_digit() => Ok(format!("_{}", identifier)),
+        Some(_) => Ok(identifier),
+        None => Err(Error::InvalidIdentifier(name.to_owned())),
+    }
+}
+
+fn escape_keyword(identifier: String) -> String {
+    if RUST_KEYWORDS.contains(&identifier.as_str()) {
+        format!("{}_", identifier)
+    } else {
+        identifier
+    }
+}
+
+/// `hero-run` to `hero_run`.
+pub fn snake_case(value: &str) -> Result<String, Error> {
+    valid(
+        value,
+        words(value)
+            .map(|w| w.to_ascii_lowercase())
+            .collect::<Vec<String>>()
+            .join("_"),
+    )
+    .map(escape_keyword)
+}
+
+/// `hero-run` to `HERO_RUN`.
+pub fn upper_snake_case(value: &str) -> Result<String, Error> {
+    valid(
+        value,
+        words(value)
+            .map(|w| w.to_ascii_uppercase())
+            .collect::<Vec<String>>()
+            .join("_"),
+    )
+}
+
+/// `hero-run` to `HeroRun`.
+pub fn pascal_case(value: &str) -> Result<String, Error> {
+    valid(
+        value,
+        words(value)
+            .map(|w| {
+                let mut chars = w.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect(),
+    )
+    .map(escape_keyword)
+}
+
+/// Pairs every name with it's identifier, two names can't yield the same identifier.
+pub fn unique<'a>(
+    names: &[&'a str],
+    convert: fn(&str) -> Result<String, Error>,
+) -> Result<Vec<(&'a str, String)>, Error> {
+    let identifiers = names
+        .iter()
+        .map(|name| Ok((*name, convert(name)?)))
+        .collect::<Result<Vec<(&str, String)>, Error>>()?;
+
+    ensure_unique(&identifiers)?;
+    Ok(identifiers)
+}
+
+/// Fails when two names are paired with the same identifier.
+pub fn ensure_unique<N: AsRef<str>>(identifiers: &[(N, String)]) -> Result<(), Error> {
+    let mut seen: HashMap<&str, &str> = HashMap::with_capacity(identifiers.len());
+
+    for (name, identifier) in identifiers {
+        if let Some(other) = seen.insert(identifier.as_str(), name.as_ref()) {
+            return Err(Error::IdentifierCollision {
+                identifier: identifier.clone(),
+                names: (other.to_owned(), name.as_ref().to_owned()),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snake_case_conversion() {
+        assert_eq!(snake_case("Hero Run-Left").unwrap(), "hero_run_left");
+        assert_eq!(snake_case("type").unwrap(), "type_");
+        assert_eq!(snake_case("2d/tiles").unwrap(), "_2d_tiles");
+        assert!(matches!(
+            snake_case("--"),
+            Err(Error::InvalidIdentifier(name)) if name == "--"
+        ));
+    }
+
+    #[test]
+    fn pascal_case_conversion() {
+        assert_eq!(pascal_case("hero-run/left").unwrap(), "HeroRunLeft");
+        assert_eq!(pascal_case("self").unwrap(), "Self_");
+        assert_eq!(pascal_case("1up").unwrap(), "_1up");
+        assert!(pascal_case("").is_err());
+    }
+
+    #[test]
+    fn unique_rejects_collisions() {
+        let identifiers = unique(&["fn", "hero-run"], snake_case).unwrap();
+        assert_eq!(
+            identifiers,
+            vec![
+                ("fn", "fn_".to_owned()),
+                ("hero-run", "hero_run".to_owned())
+            ]
+        );
+
+        assert!(matches!(
+            unique(&["hero-run", "hero_run"], snake_case),
+            Err(Error::IdentifierCollision { identifier, names })
+                if identifier == "hero_run" && names == ("hero-run".to_owned(), "hero_run".to_owned())
+        ));
+        assert!(matches!(
+            unique(&["hero", "%"], snake_case),
+            Err(Error::InvalidIdentifier(_))
+        ));
+    }
+}
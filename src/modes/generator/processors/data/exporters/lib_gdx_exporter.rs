@@ -0,0 +1,89 @@
+use std::io::Write;
+
+use super::{
+    super::{AtlasData, FrameData},
+    DataExporter, ExportContext, ExportFiles,
+};
+
+/// libGDX TextureAtlas text format, also read by Spine runtimes.
+pub struct LibGdxExporter;
+
+impl LibGdxExporter {
+    fn filename(atlas_name: &str) -> String {
+        format!("{}.atlas", atlas_name)
+    }
+}
+
+impl DataExporter for LibGdxExporter {
+    fn name(&self) -> &str {
+        "libgdx-atlas"
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![Self::filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let mut w = files.create(&Self::filename(&context.name))?;
+
+        // page header
+        writeln!(w, "{}", context.image)?;
+        writeln!(
+            w,
+            "size: {}, {}",
+            context.atlas_size.width, context.atlas_size.height
+        )?;
+        writeln!(w, "format: RGBA8888")?;
+        writeln!(w, "filter: Nearest, Nearest")?;
+        writeln!(w, "repeat: none")?;
+
+        let names: Vec<&String> = atlas_data.graphics.keys().collect();
+
+        for name in names {
+            let graphic = &atlas_data.graphics[name];
+            let canvas = graphic.canvas_size();
+
+            // single images aren't indexed
+            let indexed = graphic.frames.len() > 1 || !graphic.tracks.is_empty();
+
+            for (index, frame) in graphic.frames.iter().enumerate() {
+                if let FrameData::Contents {
+                    atlas_region,
+                    source_region,
+                    ..
+                } = frame
+                {
+                    writeln!(w, "{}", name)?;
+                    writeln!(w, "  rotate: false")?;
+                    writeln!(w, "  xy: {}, {}", atlas_region.x, atlas_region.y)?;
+                    writeln!(w, "  size: {}, {}", atlas_region.width, atlas_region.height)?;
+                    writeln!(w, "  orig: {}, {}", canvas.width, canvas.height)?;
+
+                    // offset is measured from canvas' bottom left corner
+                    writeln!(
+                        w,
+                        "  offset: {}, {}",
+                        source_region.x,
+                        canvas
+                            .height
+                            .saturating_sub(source_region.y + source_region.height)
+                    )?;
+
+                    if indexed {
+                        writeln!(w, "  index: {}", index)?;
+                    } else {
+                        writeln!(w, "  index: -1")?;
+                    }
+                }
+            }
+        }
+
+        w.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use std::io::Write;
+
+use super::{
+    super::{AtlasData, MetaData, SaveError},
+    DataExporter, ExportContext, ExportFiles,
+};
+
+/// MessagePack encoding of clymene's json format, same schema using named fields.
+pub struct MessagePackExporter;
+
+impl MessagePackExporter {
+    fn filename(atlas_name: &str) -> String {
+        format!("{}.data.msgpack", atlas_name)
+    }
+}
+
+impl DataExporter for MessagePackExporter {
+    fn name(&self) -> &str {
+        "msgpack"
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![Self::filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let mut w = files.create(&Self::filename(&context.name))?;
+        if context.nested_graphics || context.format_version != MetaData::FORMAT_VERSION {
+            let versioned =
+                atlas_data.versioned(context.format_version, context.nested_graphics)?;
+
+            rmp_serde::encode::write_named(&mut w, &versioned).map_err(SaveError::MessagePack)?;
+        } else {
+            rmp_serde::encode::write_named(&mut w, atlas_data).map_err(SaveError::MessagePack)?;
+        }
+        w.flush()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+mod binary_exporter;
+mod c_header_exporter;
+mod clymene_json_exporter;
+mod cocos_plist_exporter;
+mod css_exporter;
+mod data_exporter;
+mod export_context;
+mod export_files;
+mod godot_exporter;
+mod html_preview_exporter;
+mod identifier;
+mod lib_gdx_exporter;
+mod message_pack_exporter;
+mod rust_exporter;
+mod sparrow_exporter;
+mod template_exporter;
+mod texture_packer_exporter;
+mod texture_packer_layout;
+mod xml;
+
+pub use binary_exporter::BinaryExporter;
+pub use c_header_exporter::CHeaderExporter;
+pub use clymene_json_exporter::ClymeneJsonExporter;
+pub use cocos_plist_exporter::CocosPlistExporter;
+pub use css_exporter::CssExporter;
+pub use data_exporter::DataExporter;
+pub use export_context::ExportContext;
+pub use export_files::ExportFiles;
+pub use godot_exporter::GodotExporter;
+pub use html_preview_exporter::HtmlPreviewExporter;
+pub use lib_gdx_exporter::LibGdxExporter;
+pub use message_pack_exporter::MessagePackExporter;
+pub use rust_exporter::RustExporter;
+pub use sparrow_exporter::SparrowExporter;
+pub use template_exporter::TemplateExporter;
+pub use texture_packer_exporter::TexturePackerExporter;
+pub use texture_packer_layout::TexturePackerLayout;
@@ -0,0 +1,213 @@
+use std::io::Write;
+
+use crate::graphics::animation::FrameIndices;
+
+use super::{
+    super::{AtlasData, FrameData},
+    identifier, DataExporter, ExportContext, ExportFiles,
+};
+
+/// Rust module with typed constants, meant to be used through `include!`.
+pub struct RustExporter;
+
+impl RustExporter {
+    fn filename(atlas_name: &str) -> String {
+        format!("{}.rs", atlas_name)
+    }
+}
+
+impl DataExporter for RustExporter {
+    fn name(&self) -> &str {
+        "rust"
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![Self::filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let names: Vec<&str> = atlas_data.graphics.keys().map(String::as_str).collect();
+
+        let modules = identifier::unique(&names, identifier::snake_case)?;
+        let variants = identifier::unique(&names, identifier::pascal_case)?;
+
+        let mut w = files.create(&Self::filename(&context.name))?;
+
+        writeln!(w, "// Generated by clymene, do not edit.")?;
+        writeln!(w)?;
+        writeln!(w, "#[derive(Clone, Copy, Debug, PartialEq, Eq)]")?;
+        writeln!(w, "pub struct Rect {{")?;
+        writeln!(w, "    pub x: u32,")?;
+        writeln!(w, "    pub y: u32,")?;
+        writeln!(w, "    pub width: u32,")?;
+        writeln!(w, "    pub height: u32,")?;
+        writeln!(w, "}}")?;
+        writeln!(w)?;
+        writeln!(w, "/// Empty frames have every value as zero.")?;
+        writeln!(w, "#[derive(Clone, Copy, Debug, PartialEq, Eq)]")?;
+        writeln!(w, "pub struct Frame {{")?;
+        writeln!(w, "    pub atlas: Rect,")?;
+        writeln!(w, "    pub source: Rect,")?;
+        writeln!(w, "    /// Milliseconds, zero when undefined.")?;
+        writeln!(w, "    pub duration: u32,")?;
+        writeln!(w, "}}")?;
+        writeln!(w)?;
+        writeln!(w, "pub const IMAGE: &str = {:?};", context.image)?;
+        writeln!(w, "pub const WIDTH: u32 = {};", context.atlas_size.width)?;
+        writeln!(w, "pub const HEIGHT: u32 = {};", context.atlas_size.height)?;
+        writeln!(w, "pub const SCALE: u32 = {};", context.scale)?;
+
+        for (name, module) in &modules {
+            let graphic = &atlas_data.graphics[*name];
+            let canvas = graphic.canvas_size();
+
+            writeln!(w)?;
+            writeln!(w, "pub mod {} {{", module)?;
+            writeln!(w, "    #[allow(unused_imports)]")?;
+            writeln!(w, "    use super::{{Frame, Rect}};")?;
+            writeln!(w)?;
+            writeln!(w, "    pub const NAME: &str = {:?};", name)?;
+            writeln!(w, "    pub const CANVAS_WIDTH: u32 = {};", canvas.width)?;
+            writeln!(w, "    pub const CANVAS_HEIGHT: u32 = {};", canvas.height)?;
+            writeln!(
+                w,
+                "    pub const FRAMES: [Frame; {}] = [",
+                graphic.frames.len()
+            )?;
+
+            for frame in &graphic.frames {
+                match frame {
+                    FrameData::Empty => writeln!(
+                        w,
+                        "        Frame {{ atlas: {}, source: {}, duration: 0 }},",
+                        rect(0, 0, 0, 0),
+                        rect(0, 0, 0, 0)
+                    )?,
+                    FrameData::Contents {
+                        atlas_region,
+                        duration,
+                        source_region,
+                        ..
+                    } => writeln!(
+                        w,
+                        "        Frame {{ atlas: {}, source: {}, duration: {} }},",
+                        rect(
+                            atlas_region.x,
+                            atlas_region.y,
+                            atlas_region.width,
+                            atlas_region.height
+                        ),
+                        rect(
+                            source_region.x,
+                            source_region.y,
+                            source_region.width,
+                            source_region.height
+                        ),
+                        duration.unwrap_or(0)
+                    )?,
+                }
+            }
+
+            writeln!(w, "    ];")?;
+
+            let labeled_tracks = graphic.tracks.labeled_entries("/");
+
+            if !labeled_tracks.is_empty() {
+                let labels: Vec<&str> = labeled_tracks.iter().map(|(l, _)| l.as_str()).collect();
+                let track_consts = identifier::unique(&labels, identifier::upper_snake_case)?;
+
+                writeln!(w)?;
+                writeln!(w, "    /// Frame index ranges, to be used at `FRAMES`.")?;
+                writeln!(w, "    pub mod tracks {{")?;
+                writeln!(w, "        use core::ops::RangeInclusive;")?;
+                writeln!(w)?;
+
+                for ((_, track), (_, track_const)) in labeled_tracks.iter().zip(track_consts) {
+                    let ranges: Vec<String> = track
+                        .indices()
+                        .into_iter()
+                        .map(|indices| match indices {
+                            FrameIndices::Value(i) => {
+                                format!("{}..={}", i, i)
+                            }
+                            FrameIndices::Range { from, to } => {
+                                format!("{}..={}", from, to)
+                            }
+                        })
+                        .collect();
+
+                    writeln!(
+                        w,
+                        "        pub const {}: &[RangeInclusive<usize>] = &[{}];",
+                        track_const,
+                        ranges.join(", ")
+                    )?;
+                }
+
+                writeln!(w, "    }}")?;
+            }
+
+            writeln!(w, "}}")?;
+        }
+
+        // enum to refer graphics
+        writeln!(w)?;
+        writeln!(w, "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]")?;
+        writeln!(w, "pub enum Graphic {{")?;
+
+        for (_, variant) in &variants {
+            writeln!(w, "    {},", variant)?;
+        }
+
+        writeln!(w, "}}")?;
+        writeln!(w)?;
+        writeln!(w, "impl Graphic {{")?;
+        writeln!(w, "    pub const ALL: [Graphic; {}] = [", variants.len())?;
+
+        for (_, variant) in &variants {
+            writeln!(w, "        Graphic::{},", variant)?;
+        }
+
+        writeln!(w, "    ];")?;
+        writeln!(w)?;
+        writeln!(w, "    pub fn name(self) -> &'static str {{")?;
+        writeln!(w, "        match self {{")?;
+
+        for ((_, variant), (_, module)) in variants.iter().zip(&modules) {
+            writeln!(w, "            Graphic::{} => {}::NAME,", variant, module)?;
+        }
+
+        writeln!(w, "        }}")?;
+        writeln!(w, "    }}")?;
+        writeln!(w)?;
+        writeln!(w, "    pub fn frames(self) -> &'static [Frame] {{")?;
+        writeln!(w, "        match self {{")?;
+
+        for ((_, variant), (_, module)) in variants.iter().zip(&modules) {
+            writeln!(
+                w,
+                "            Graphic::{} => &{}::FRAMES,",
+                variant, module
+            )?;
+        }
+
+        writeln!(w, "        }}")?;
+        writeln!(w, "    }}")?;
+        writeln!(w, "}}")?;
+
+        w.flush()?;
+        Ok(())
+    }
+}
+
+fn rect(x: u32, y: u32, width: u32, height: u32) -> String {
+    format!(
+        "Rect {{ x: {}, y: {}, width: {}, height: {} }}",
+        x, y, width, height
+    )
+}
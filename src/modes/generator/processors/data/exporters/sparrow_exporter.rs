@@ -0,0 +1,84 @@
+use std::io::Write;
+
+use super::{
+    super::{AtlasData, FrameData},
+    xml, DataExporter, ExportContext, ExportFiles,
+};
+
+/// Sparrow/Starling TextureAtlas xml, as used by HaxeFlixel.
+pub struct SparrowExporter;
+
+impl SparrowExporter {
+    fn filename(atlas_name: &str) -> String {
+        format!("{}.xml", atlas_name)
+    }
+}
+
+impl DataExporter for SparrowExporter {
+    fn name(&self) -> &str {
+        "sparrow"
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![Self::filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let mut w = files.create(&Self::filename(&context.name))?;
+
+        writeln!(w, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            w,
+            "<TextureAtlas imagePath=\"{}\">",
+            xml::escape(&context.image)
+        )?;
+
+        let names: Vec<&String> = atlas_data.graphics.keys().collect();
+
+        for name in names {
+            let graphic = &atlas_data.graphics[name];
+            let canvas = graphic.canvas_size();
+            let is_animation = graphic.frames.len() > 1 || !graphic.tracks.is_empty();
+
+            for (index, frame) in graphic.frames.iter().enumerate() {
+                if let FrameData::Contents {
+                    atlas_region,
+                    source_region,
+                    ..
+                } = frame
+                {
+                    // animation frames are found by name prefix, followed by a 4 digits index
+                    let subtexture_name = if is_animation {
+                        format!("{}{:04}", name, index)
+                    } else {
+                        name.clone()
+                    };
+
+                    writeln!(
+                        w,
+                        "    <SubTexture name=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" frameX=\"{}\" frameY=\"{}\" frameWidth=\"{}\" frameHeight=\"{}\"/>",
+                        xml::escape(&subtexture_name),
+                        atlas_region.x,
+                        atlas_region.y,
+                        atlas_region.width,
+                        atlas_region.height,
+                        -(source_region.x as i64),
+                        -(source_region.y as i64),
+                        canvas.width,
+                        canvas.height
+                    )?;
+                }
+            }
+        }
+
+        writeln!(w, "</TextureAtlas>")?;
+        w.flush()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use std::{fs, io::Write, path::Path};
+
+use eyre::{eyre, WrapErr};
+use serde_json::{json, Value};
+
+use super::{
+    super::{templating::Template, AtlasData, FrameData},
+    DataExporter, ExportContext, ExportFiles,
+};
+
+/// Renders atlas data through an user provided template.
+///
+/// Output file is named after template file, without it's last extension,
+/// e.g `my_engine.json.tpl` yields `{atlas}.my_engine.json`.
+pub struct TemplateExporter {
+    name: String,
+    suffix: String,
+    template: Template,
+}
+
+impl TemplateExporter {
+    pub fn load<P: AsRef<Path>>(path: P) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let suffix = match path.file_stem() {
+            Some(stem) => stem.to_string_lossy().into_owned(),
+            None => {
+                return Err(eyre!(
+                    "Template path '{}' hasn't a filename",
+                    path.display()
+                ))
+            }
+        };
+
+        let source = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read template '{}'", path.display()))?;
+
+        let template = Template::parse(&source)
+            .wrap_err_with(|| format!("Failed to parse template '{}'", path.display()))?;
+
+        Ok(Self {
+            name: format!("template:{}", path.display()),
+            suffix,
+            template,
+        })
+    }
+
+    /// Output filename after atlas name, shared by templates with the same file stem.
+    pub fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    fn filename(&self, atlas_name: &str) -> String {
+        format!("{}.{}", atlas_name, self.suffix)
+    }
+
+    /// Values available to templates.
+    fn data(atlas_data: &AtlasData, context: &ExportContext) -> Value {
+        let names: Vec<&String> = atlas_data.graphics.keys().collect();
+
+        let graphics: Vec<Value> = names
+            .into_iter()
+            .map(|name| {
+                let graphic = &atlas_data.graphics[name];
+                let canvas = graphic.canvas_size();
+
+                let frames: Vec<Value> = graphic
+                    .frames
+                    .iter()
+                    .enumerate()
+                    .map(|(index, frame)| match frame {
+                        FrameData::Empty => json!({ "index": index, "empty": true }),
+                        FrameData::Contents {
+                            atlas_region,
+                            duration,
+                            source_region,
+                            pivot,
+                            hitboxes,
+                        } => json!({
+                            "index": index,
+                            "empty": false,
+                            "atlas": atlas_region,
+                            "source": source_region,
+                            "duration": duration,
+                            "pivot": pivot,
+                            "hitboxes": hitboxes,
+                        }),
+                    })
+                    .collect();
+
+                let tracks: Vec<Value> = graphic
+                    .tracks
+                    .labeled_entries("/")
+                    .into_iter()
+                    .map(|(label, track)| {
+                        json!({
+                            "label": label,
+                            "frames": track.indices().frames().collect::<Vec<u32>>(),
+                        })
+                    })
+                    .collect();
+
+                json!({
+                    "name": name,
+                    "canvas": canvas,
+                    "animated": graphic.frames.len() > 1 || !graphic.tracks.is_empty(),
+                    "frames": frames,
+                    "tracks": tracks,
+                })
+            })
+            .collect();
+
+        json!({
+            "atlas": {
+                "name": context.name,
+                "image": context.image,
+                "width": context.atlas_size.width,
+                "height": context.atlas_size.height,
+                "scale": context.scale,
+                "premultiplied_alpha": context.premultiplied_alpha,
+            },
+            "graphics": graphics,
+            "meta": atlas_data.meta,
+        })
+    }
+}
+
+impl DataExporter for TemplateExporter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![self.filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let mut w = files.create(&self.filename(&context.name))?;
+        w.write_all(
+            self.template
+                .render(&Self::data(atlas_data, context))
+                .as_bytes(),
+        )?;
+        w.flush()?;
+
+        Ok(())
+    }
+}
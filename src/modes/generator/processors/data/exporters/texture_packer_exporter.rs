@@ -0,0 +1,243 @@
+use std::{collections::BTreeMap, io::Write};
+
+use serde::{Serialize, Serializer};
+
+use crate::math::{Point, Rectangle, Size};
+
+use super::{
+    super::{AtlasData, FrameData, SaveError},
+    DataExporter, ExportContext, ExportFiles, TexturePackerLayout,
+};
+
+/// TexturePacker's json format, as consumed by Phaser, PixiJS and Cocos.
+pub struct TexturePackerExporter {
+    layout: TexturePackerLayout,
+}
+
+impl TexturePackerExporter {
+    pub fn new(layout: TexturePackerLayout) -> Self {
+        Self { layout }
+    }
+
+    fn filename(&self, atlas_name: &str) -> String {
+        match self.layout {
+            TexturePackerLayout::Hash => format!("{}.texturepacker.json", atlas_name),
+            TexturePackerLayout::Array => format!("{}.texturepacker-array.json", atlas_name),
+        }
+    }
+
+    fn frame_key(name: &str, index: usize) -> String {
+        format!("{}/{}", name, index)
+    }
+}
+
+impl DataExporter for TexturePackerExporter {
+    fn name(&self) -> &str {
+        match self.layout {
+            TexturePackerLayout::Hash => "texturepacker-hash",
+            TexturePackerLayout::Array => "texturepacker-array",
+        }
+    }
+
+    fn filenames(&self, atlas_name: &str, _atlas_data: &AtlasData) -> Vec<String> {
+        vec![self.filename(atlas_name)]
+    }
+
+    fn export(
+        &self,
+        atlas_data: &AtlasData,
+        context: &ExportContext,
+        files: &mut ExportFiles,
+    ) -> eyre::Result<()> {
+        let mut frames = Vec::new();
+        let mut animations = BTreeMap::new();
+
+        let names: Vec<&String> = atlas_data.graphics.keys().collect();
+
+        for name in names {
+            let graphic = &atlas_data.graphics[name];
+            let canvas = graphic.canvas_size();
+
+            for (index, frame) in graphic.frames.iter().enumerate() {
+                // empty frames have nothing at atlas to refer to
+                if let FrameData::Contents {
+                    atlas_region,
+                    duration,
+                    source_region,
+                    pivot,
+                    ..
+                } = frame
+                {
+                    frames.push((
+                        Self::frame_key(name, index),
+                        FrameEntry {
+                            filename: None,
+                            frame: atlas_region.into(),
+                            rotated: false,
+                            trimmed: source_region.x != 0
+                                || source_region.y != 0
+                                || source_region.width != canvas.width
+                                || source_region.height != canvas.height,
+                            sprite_source_size: source_region.into(),
+                            source_size: (&canvas).into(),
+                            duration: *duration,
+                            pivot: pivot.as_ref().map(|p| Anchor::new(&p.canvas, &canvas)),
+                        },
+                    ));
+                }
+            }
+
+            for (label, track) in graphic.tracks.labeled_entries("/") {
+                let keys = track
+                    .indices()
+                    .frames()
+                    .map(|i| i as usize)
+                    .filter(|i| matches!(graphic.frames.get(*i), Some(FrameData::Contents { .. })))
+                    .map(|i| Self::frame_key(name, i))
+                    .collect();
+
+                animations.insert(format!("{}/{}", name, label), keys);
+            }
+        }
+
+        let document = Document {
+            frames: match self.layout {
+                TexturePackerLayout::Hash => Frames::Hash(FrameMap(frames)),
+                TexturePackerLayout::Array => Frames::Array(
+                    frames
+                        .into_iter()
+                        .map(|(key, entry)| FrameEntry {
+                            filename: Some(key),
+                            ..entry
+                        })
+                        .collect(),
+                ),
+            },
+            animations,
+            meta: Meta {
+                app: &atlas_data.meta.app,
+                version: &atlas_data.meta.version,
+                image: &context.image,
+                format: "RGBA8888",
+                size: (&context.atlas_size).into(),
+                scale: context.scale.to_string(),
+            },
+        };
+
+        let mut writer = files.create(&self.filename(&context.name))?;
+
+        if context.prettify {
+            serde_json::to_writer_pretty(&mut writer, &document).map_err(SaveError::Serialize)?;
+        } else {
+            serde_json::to_writer(&mut writer, &document).map_err(SaveError::Serialize)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    frames: Frames,
+
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    animations: BTreeMap<String, Vec<String>>,
+
+    meta: Meta<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Frames {
+    Hash(FrameMap),
+    Array(Vec<FrameEntry>),
+}
+
+/// Frames keyed by name, keeping insertion order.
+struct FrameMap(Vec<(String, FrameEntry)>);
+
+impl Serialize for FrameMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(key, entry)| (key, entry)))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FrameEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filename: Option<String>,
+
+    frame: Rect,
+    rotated: bool,
+    trimmed: bool,
+    sprite_source_size: Rect,
+    source_size: Dimensions,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pivot: Option<Anchor>,
+}
+
+#[derive(Serialize)]
+struct Meta<'a> {
+    app: &'a str,
+    version: &'a str,
+    image: &'a str,
+    format: &'a str,
+    size: Dimensions,
+    scale: String,
+}
+
+#[derive(Serialize)]
+struct Rect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+impl From<&Rectangle<u32>> for Rect {
+    fn from(rect: &Rectangle<u32>) -> Self {
+        Self {
+            x: rect.x,
+            y: rect.y,
+            w: rect.width,
+            h: rect.height,
+        }
+    }
+}
+
+/// Point normalized by source size.
+#[derive(Serialize)]
+struct Anchor {
+    x: f32,
+    y: f32,
+}
+
+impl Anchor {
+    fn new(point: &Point<i32>, size: &Size<u32>) -> Self {
+        Self {
+            x: point.x as f32 / size.width.max(1) as f32,
+            y: point.y as f32 / size.height.max(1) as f32,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Dimensions {
+    w: u32,
+    h: u32,
+}
+
+impl From<&Size<u32>> for Dimensions {
+    fn from(size: &Size<u32>) -> Self {
+        Self {
+            w: size.width,
+            h: size.height,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+/// How TexturePacker json frames are laid out.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TexturePackerLayout {
+    /// Frames as an object, keyed by frame name.
+    Hash,
+
+    /// Frames as a list, each one with it's `filename`.
+    Array,
+}
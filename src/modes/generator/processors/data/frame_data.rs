@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::{collections::BTreeMap, convert::TryInto};
 
 use serde::{Deserialize, Serialize};
 
@@ -6,6 +6,8 @@ use crate::{
     math::Rectangle, modes::generator::processors::image::format_handlers::aseprite_handler,
 };
 
+use super::PivotData;
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(untagged)]
 pub enum FrameData {
@@ -19,9 +21,41 @@ pub enum FrameData {
 
         #[serde(rename = "source")]
         source_region: Rectangle<u32>,
+
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        pivot: Option<PivotData>,
+
+        /// Named regions at source canvas, e.g `hurtbox`.
+        #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+        hitboxes: BTreeMap<String, Rectangle<u32>>,
     },
 }
 
+impl FrameData {
+    /// Atlas and source regions multiplied by an integer scale factor.
+    pub fn scaled(&self, scale: u32) -> Self {
+        match self {
+            FrameData::Empty => FrameData::Empty,
+            FrameData::Contents {
+                atlas_region,
+                duration,
+                source_region,
+                pivot,
+                hitboxes,
+            } => FrameData::Contents {
+                atlas_region: atlas_region.scaled(scale),
+                duration: *duration,
+                source_region: source_region.scaled(scale),
+                pivot: pivot.as_ref().map(|p| p.scaled(scale)),
+                hitboxes: hitboxes
+                    .iter()
+                    .map(|(name, hitbox)| (name.clone(), hitbox.scaled(scale)))
+                    .collect(),
+            },
+        }
+    }
+}
+
 impl From<&aseprite_handler::data::FrameData> for FrameData {
     fn from(aseprite_frame_data: &aseprite_handler::data::FrameData) -> Self {
         if aseprite_frame_data.sprite_source_size.w == 0
@@ -45,6 +79,8 @@ impl From<&aseprite_handler::data::FrameData> for FrameData {
                 aseprite_frame_data.sprite_source_size.h,
             )
             .unwrap_or_default(),
+            pivot: None,
+            hitboxes: BTreeMap::new(),
         }
     }
 }
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::graphics::animation::TrackList;
+use crate::{graphics::animation::TrackList, math::Size};
 
 use super::FrameData;
 
@@ -8,6 +8,10 @@ use super::FrameData;
 pub struct GraphicData {
     pub frames: Vec<FrameData>,
     pub tracks: TrackList,
+
+    /// Source canvas size, before empty space is cropped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canvas: Option<Size<u32>>,
 }
 
 impl GraphicData {
@@ -15,6 +19,33 @@ impl GraphicData {
         Self {
             frames: Vec::new(),
             tracks: TrackList::new(),
+            canvas: None,
+        }
+    }
+
+    /// Source canvas size, inferred from frames' source regions when it isn't known.
+    pub fn canvas_size(&self) -> Size<u32> {
+        if let Some(canvas) = &self.canvas {
+            return canvas.clone();
+        }
+
+        self.frames
+            .iter()
+            .fold(Size::default(), |size, frame| match frame {
+                FrameData::Empty => size,
+                FrameData::Contents { source_region, .. } => Size::new(
+                    size.width.max(source_region.x + source_region.width),
+                    size.height.max(source_region.y + source_region.height),
+                ),
+            })
+    }
+
+    /// Copy with every frame region multiplied by an integer scale factor.
+    pub fn scaled(&self, scale: u32) -> Self {
+        Self {
+            frames: self.frames.iter().map(|f| f.scaled(scale)).collect(),
+            tracks: self.tracks.clone(),
+            canvas: self.canvas.as_ref().map(|c| c.scaled(scale)),
         }
     }
 }
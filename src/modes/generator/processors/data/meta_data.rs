@@ -1,16 +1,67 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{math::Size, settings::ImageFormat};
+
+use super::TextureData;
+
 #[derive(Serialize, Deserialize)]
 pub struct MetaData {
     pub app: String,
     pub version: String,
+
+    /// Data format version, data without it is at legacy version.
+    #[serde(default = "MetaData::legacy_format_version")]
+    pub format_version: u32,
+
+    /// Main atlas image filename, relative to data file.
+    #[serde(default)]
+    pub image: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<ImageFormat>,
+
+    /// Atlas image size, already scaled.
+    #[serde(default)]
+    pub size: Size<u32>,
+
+    #[serde(default = "MetaData::default_scale")]
+    pub scale: u32,
+
+    /// FNV-1a 64 bits hash, as hex, of main atlas image decoded rgba pixels.
+    /// Only available at uncompressed image formats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub textures: Vec<TextureData>,
 }
 
 impl MetaData {
+    /// Latest data format version, it changes only when format breaks compatibility.
+    pub const FORMAT_VERSION: u32 = 2;
+
+    /// Data format version before it was versioned.
+    pub const LEGACY_FORMAT_VERSION: u32 = 1;
+
     pub fn new() -> Self {
         Self {
             app: String::from("https://github.com/lucas-miranda/clymene"),
             version: env!("CARGO_PKG_VERSION").to_owned(),
+            format_version: MetaData::FORMAT_VERSION,
+            image: String::new(),
+            format: None,
+            size: Size::default(),
+            scale: MetaData::default_scale(),
+            hash: None,
+            textures: Vec::new(),
         }
     }
+
+    fn legacy_format_version() -> u32 {
+        MetaData::LEGACY_FORMAT_VERSION
+    }
+
+    fn default_scale() -> u32 {
+        1
+    }
 }
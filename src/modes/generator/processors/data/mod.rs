@@ -1,9 +1,13 @@
 mod atlas_data;
 mod data_processor;
 mod error;
+pub mod exporters;
 mod frame_data;
 mod graphic_data;
 mod meta_data;
+mod pivot_data;
+mod templating;
+mod texture_data;
 
 pub use atlas_data::AtlasData;
 pub use data_processor::DataProcessor;
@@ -11,3 +15,5 @@ pub use error::{Error, SaveError};
 pub use frame_data::FrameData;
 pub use graphic_data::GraphicData;
 pub use meta_data::MetaData;
+pub use pivot_data::PivotData;
+pub use texture_data::TextureData;
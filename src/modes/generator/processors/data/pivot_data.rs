@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::math::{Point, Rectangle};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PivotData {
+    /// Position at source canvas.
+    pub canvas: Point<i32>,
+
+    /// Position relative to trimmed source region.
+    pub source: Point<i32>,
+}
+
+impl PivotData {
+    pub fn new(canvas: Point<i32>, source_region: &Rectangle<u32>) -> Self {
+        Self {
+            source: Point::new(
+                canvas.x - source_region.x as i32,
+                canvas.y - source_region.y as i32,
+            ),
+            canvas,
+        }
+    }
+
+    /// Positions multiplied by an integer scale factor.
+    pub fn scaled(&self, scale: u32) -> Self {
+        Self {
+            canvas: self.canvas.scaled(scale as i32),
+            source: self.source.scaled(scale as i32),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod node;
+mod template;
+mod template_error;
+
+pub use template::Template;
+pub use template_error::TemplateError;
@@ -0,0 +1,21 @@
+/// Parsed template piece.
+#[derive(Debug)]
+pub enum Node {
+    Text(String),
+
+    /// `{{path}}`
+    Variable(String),
+
+    /// `{{#each path}} body {{/each}}`
+    Each {
+        path: String,
+        body: Vec<Node>,
+    },
+
+    /// `{{#if path}} then {{else}} otherwise {{/if}}`
+    If {
+        path: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
@@ -0,0 +1,453 @@
+use std::borrow::Cow;
+
+use serde_json::Value;
+
+use super::{node::Node, TemplateError};
+
+/// Minimal text template, rendered from a json value.
+///
+/// Supports `{{path.to.value}}`, `{{#each path}}...{{/each}}`,
+/// `{{#if path}}...{{else}}...{{/if}}` and `{{! comments }}`.
+/// Inside an each block, `this`, `@index`, `@key`, `@first` and `@last` refers to current item,
+/// any other path is looked up from inner to outer scope.
+#[derive(Debug)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        let mut tokens = tokenize(source)?;
+        trim_standalone_tags(&mut tokens);
+
+        let mut tokens = tokens.into_iter();
+        let (nodes, terminator) = parse_nodes(&mut tokens)?;
+
+        match terminator {
+            Terminator::End => Ok(Self { nodes }),
+            Terminator::Else { line } => Err(TemplateError::UnexpectedTag {
+                tag: "else".to_owned(),
+                line,
+            }),
+            Terminator::Close { name, line } => Err(TemplateError::UnexpectedTag {
+                tag: format!("/{}", name),
+                line,
+            }),
+        }
+    }
+
+    pub fn render(&self, data: &Value) -> String {
+        let mut output = String::new();
+        let mut scopes = vec![Scope {
+            value: data,
+            index: None,
+            key: None,
+            len: 0,
+        }];
+
+        render_nodes(&self.nodes, &mut scopes, &mut output);
+        output
+    }
+}
+
+enum Token {
+    Text(String),
+    Tag { content: String, line: usize },
+}
+
+enum Terminator {
+    End,
+    Else { line: usize },
+    Close { name: String, line: usize },
+}
+
+struct Scope<'a> {
+    value: &'a Value,
+    index: Option<usize>,
+    key: Option<&'a str>,
+    len: usize,
+}
+
+/// Splits source into text and tags, text tokens are always at even positions.
+fn tokenize(source: &str) -> Result<Vec<Token>, TemplateError> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    let mut line = 1;
+
+    while let Some(start) = rest.find("{{") {
+        let text = &rest[..start];
+        line += text.matches('\n').count();
+        tokens.push(Token::Text(text.to_owned()));
+
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or(TemplateError::UnclosedTag { line })?;
+
+        tokens.push(Token::Tag {
+            content: after[..end].trim().to_owned(),
+            line,
+        });
+
+        line += after[..end].matches('\n').count();
+        rest = &after[end + 2..];
+    }
+
+    tokens.push(Token::Text(rest.to_owned()));
+    Ok(tokens)
+}
+
+/// Removes lines which only contains a block tag, so blocks doesn't leave blank lines behind.
+fn trim_standalone_tags(tokens: &mut [Token]) {
+    let last_index = tokens.len() - 1;
+
+    let standalone: Vec<usize> = (1..last_index)
+        .step_by(2)
+        .filter(|i| {
+            let is_block = match &tokens[*i] {
+                Token::Tag { content, .. } => {
+                    content == "else"
+                        || content.starts_with('#')
+                        || content.starts_with('/')
+                        || content.starts_with('!')
+                }
+                Token::Text(_) => false,
+            };
+
+            if !is_block {
+                return false;
+            }
+
+            let starts_line = match &tokens[i - 1] {
+                Token::Text(text) => match text.rfind('\n') {
+                    Some(n) => text[n + 1..].trim().is_empty(),
+                    None => *i == 1 && text.trim().is_empty(),
+                },
+                Token::Tag { .. } => false,
+            };
+
+            let ends_line = match &tokens[i + 1] {
+                Token::Text(text) => match text.find('\n') {
+                    Some(n) => text[..n].trim().is_empty(),
+                    None => i + 1 == last_index && text.trim().is_empty(),
+                },
+                Token::Tag { .. } => false,
+            };
+
+            starts_line && ends_line
+        })
+        .collect();
+
+    for i in standalone {
+        if let Token::Text(text) = &mut tokens[i - 1] {
+            let len = text.rfind('\n').map_or(0, |n| n + 1);
+            text.truncate(len);
+        }
+
+        if let Token::Text(text) = &mut tokens[i + 1] {
+            let len = text.find('\n').map_or(text.len(), |n| n + 1);
+            text.drain(..len);
+        }
+    }
+}
+
+fn parse_nodes<I: Iterator<Item = Token>>(
+    tokens: &mut I,
+) -> Result<(Vec<Node>, Terminator), TemplateError> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        let (content, line) = match token {
+            Token::Text(text) => {
+                if !text.is_empty() {
+                    nodes.push(Node::Text(text));
+                }
+
+                continue;
+            }
+            Token::Tag { content, line } => (content, line),
+        };
+
+        if content.starts_with('!') {
+            continue;
+        } else if content == "else" {
+            return Ok((nodes, Terminator::Else { line }));
+        } else if let Some(name) = content.strip_prefix('/') {
+            return Ok((
+                nodes,
+                Terminator::Close {
+                    name: name.trim().to_owned(),
+                    line,
+                },
+            ));
+        } else if let Some(block) = content.strip_prefix('#') {
+            let mut parts = block.splitn(2, char::is_whitespace);
+            let name = parts.next().unwrap_or_default().to_owned();
+            let path = parts.next().unwrap_or_default().trim().to_owned();
+
+            match name.as_str() {
+                "each" => {
+                    let (body, terminator) = parse_nodes(tokens)?;
+                    expect_close(&name, line, terminator)?;
+                    nodes.push(Node::Each { path, body });
+                }
+                "if" => {
+                    let (then, terminator) = parse_nodes(tokens)?;
+
+                    let otherwise = match terminator {
+                        Terminator::Else { .. } => {
+                            let (otherwise, terminator) = parse_nodes(tokens)?;
+                            expect_close(&name, line, terminator)?;
+                            otherwise
+                        }
+                        _ => {
+                            expect_close(&name, line, terminator)?;
+                            Vec::new()
+                        }
+                    };
+
+                    nodes.push(Node::If {
+                        path,
+                        then,
+                        otherwise,
+                    });
+                }
+                _ => return Err(TemplateError::UnknownBlock { name, line }),
+            }
+        } else {
+            nodes.push(Node::Variable(content));
+        }
+    }
+
+    Ok((nodes, Terminator::End))
+}
+
+fn expect_close(name: &str, line: usize, terminator: Terminator) -> Result<(), TemplateError> {
+    match terminator {
+        Terminator::Close {
+            name: close_name, ..
+        } if close_name == name => Ok(()),
+        Terminator::Close {
+            name: close_name,
+            line,
+        } => Err(TemplateError::UnexpectedTag {
+            tag: format!("/{}", close_name),
+            line,
+        }),
+        Terminator::Else { line } => Err(TemplateError::UnexpectedTag {
+            tag: "else".to_owned(),
+            line,
+        }),
+        Terminator::End => Err(TemplateError::UnclosedBlock {
+            name: name.to_owned(),
+            line,
+        }),
+    }
+}
+
+fn render_nodes<'a>(nodes: &'a [Node], scopes: &mut Vec<Scope<'a>>, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Variable(path) => match lookup(path, scopes).as_deref() {
+                Some(Value::String(s)) => output.push_str(s),
+                Some(Value::Null) | None => (),
+                Some(value) => output.push_str(&value.to_string()),
+            },
+            Node::Each { path, body } => match lookup(path, scopes) {
+                Some(Cow::Borrowed(Value::Array(items))) => {
+                    for (index, item) in items.iter().enumerate() {
+                        scopes.push(Scope {
+                            value: item,
+                            index: Some(index),
+                            key: None,
+                            len: items.len(),
+                        });
+
+                        render_nodes(body, scopes, output);
+                        scopes.pop();
+                    }
+                }
+                Some(Cow::Borrowed(Value::Object(entries))) => {
+                    for (index, (key, item)) in entries.iter().enumerate() {
+                        scopes.push(Scope {
+                            value: item,
+                            index: Some(index),
+                            key: Some(key),
+                            len: entries.len(),
+                        });
+
+                        render_nodes(body, scopes, output);
+                        scopes.pop();
+                    }
+                }
+                _ => (),
+            },
+            Node::If {
+                path,
+                then,
+                otherwise,
+            } => {
+                if lookup(path, scopes).is_some_and(|v| is_truthy(&v)) {
+                    render_nodes(then, scopes, output);
+                } else {
+                    render_nodes(otherwise, scopes, output);
+                }
+            }
+        }
+    }
+}
+
+fn lookup<'a>(path: &str, scopes: &[Scope<'a>]) -> Option<Cow<'a, Value>> {
+    let current = scopes.last()?;
+
+    match path {
+        "this" | "." => return Some(Cow::Borrowed(current.value)),
+        "@index" => return current.index.map(|i| Cow::Owned(Value::from(i))),
+        "@key" => return current.key.map(|k| Cow::Owned(Value::from(k))),
+        "@first" => return current.index.map(|i| Cow::Owned(Value::from(i == 0))),
+        "@last" => {
+            return current
+                .index
+                .map(|i| Cow::Owned(Value::from(i + 1 == current.len)))
+        }
+        _ => (),
+    }
+
+    let mut segments = path.split('.');
+    let first = segments.next()?;
+
+    let mut value = if first == "this" {
+        current.value
+    } else {
+        scopes.iter().rev().find_map(|s| child(s.value, first))?
+    };
+
+    for segment in segments {
+        value = child(value, segment)?;
+    }
+
+    Some(Cow::Borrowed(value))
+}
+
+fn child<'a>(value: &'a Value, segment: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(entries) => entries.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(entries) => !entries.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn render(source: &str, data: Value) -> String {
+        Template::parse(source).unwrap().render(&data)
+    }
+
+    #[test]
+    fn variables() {
+        assert_eq!(
+            render(
+                "{{name}} {{size.w}}x{{size.h}} {{missing}}{{list.1}}",
+                json!({ "name": "hero", "size": { "w": 16, "h": 8 }, "list": [1, 2] })
+            ),
+            "hero 16x8 2"
+        );
+    }
+
+    #[test]
+    fn each_array() {
+        assert_eq!(
+            render(
+                "{{#each items}}{{@index}}:{{this}}{{#if @last}}.{{else}},{{/if}}{{/each}}",
+                json!({ "items": ["a", "b", "c"] })
+            ),
+            "0:a,1:b,2:c."
+        );
+    }
+
+    #[test]
+    fn each_object() {
+        assert_eq!(
+            render(
+                "{{#each items}}{{@key}}={{x}};{{/each}}",
+                json!({ "items": { "a": { "x": 1 }, "b": { "x": 2 } } })
+            ),
+            "a=1;b=2;"
+        );
+    }
+
+    #[test]
+    fn outer_scope_lookup() {
+        assert_eq!(
+            render(
+                "{{#each graphics}}{{#each frames}}{{name}}/{{this}}@{{scale}} {{/each}}{{/each}}",
+                json!({ "scale": 2, "graphics": [{ "name": "hero", "frames": [0, 1] }] })
+            ),
+            "hero/0@2 hero/1@2 "
+        );
+    }
+
+    #[test]
+    fn if_else() {
+        let source = "{{#if value}}yes{{else}}no{{/if}}";
+
+        assert_eq!(render(source, json!({ "value": 1 })), "yes");
+        assert_eq!(render(source, json!({ "value": "" })), "no");
+        assert_eq!(render(source, json!({ "value": [] })), "no");
+        assert_eq!(render(source, json!({})), "no");
+    }
+
+    #[test]
+    fn standalone_tags_lines_are_removed() {
+        assert_eq!(
+            render(
+                "begin\n{{! comment }}\n{{#each items}}\n  - {{this}}\n{{/each}}\nend\n",
+                json!({ "items": [1, 2] })
+            ),
+            "begin\n  - 1\n  - 2\nend\n"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            Template::parse("{{name"),
+            Err(TemplateError::UnclosedTag { line: 1 })
+        ));
+
+        assert!(matches!(
+            Template::parse("\n{{#each items}}"),
+            Err(TemplateError::UnclosedBlock { line: 2, .. })
+        ));
+
+        assert!(matches!(
+            Template::parse("{{#if a}}{{/each}}"),
+            Err(TemplateError::UnexpectedTag { .. })
+        ));
+
+        assert!(matches!(
+            Template::parse("{{else}}"),
+            Err(TemplateError::UnexpectedTag { .. })
+        ));
+
+        assert!(matches!(
+            Template::parse("{{#with a}}{{/with}}"),
+            Err(TemplateError::UnknownBlock { .. })
+        ));
+    }
+}
@@ -0,0 +1,35 @@
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+};
+
+#[derive(Debug)]
+pub enum TemplateError {
+    UnclosedTag { line: usize },
+    UnclosedBlock { name: String, line: usize },
+    UnexpectedTag { tag: String, line: usize },
+    UnknownBlock { name: String, line: usize },
+}
+
+impl error::Error for TemplateError {}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::UnclosedTag { line } => {
+                write!(f, "Tag at line {} isn't closed, expected '}}}}'", line)
+            }
+            TemplateError::UnclosedBlock { name, line } => write!(
+                f,
+                "Block '{}' at line {} isn't closed, expected '{{{{/{}}}}}'",
+                name, line, name
+            ),
+            TemplateError::UnexpectedTag { tag, line } => {
+                write!(f, "Unexpected tag '{{{{{}}}}}' at line {}", tag, line)
+            }
+            TemplateError::UnknownBlock { name, line } => {
+                write!(f, "Unknown block '{}' at line {}", name, line)
+            }
+        }
+    }
+}
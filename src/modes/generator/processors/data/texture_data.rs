@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use crate::settings::{ImageFormat, TextureCompression};
+
+/// Atlas image file produced alongside data.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TextureData {
+    /// Image filename.
+    pub image: String,
+
+    pub format: ImageFormat,
+
+    /// Gpu block compression, only defined at compressed formats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<TextureCompression>,
+
+    /// Companion suffix, when it isn't a base atlas page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub companion: Option<String>,
+}
@@ -26,10 +26,14 @@ use crate::{
     util,
 };
 
-use super::data::{Data, FrameData};
+use super::{
+    data::{Data, FrameData},
+    HITBOX_PREFIX,
+};
 
 const FRAME_FILE_NAME_FORMAT: &str = "{frame}.png";
 const DATA_FILE_NAME: &str = "data.json";
+const HITBOX_DATA_FILE_PREFIX: &str = "hitbox";
 
 #[derive(Default)]
 pub struct CommandProcessor {}
@@ -154,6 +158,45 @@ impl CommandProcessor {
         ase_filepath
     }
 
+    /// Exports source file data (trimmed frames regions, tags, slices and layers) to a json file.
+    fn export_data(
+        &self,
+        source_file_path: &Path,
+        data_path: &Path,
+        layer_args: &[&OsStr],
+        config: &Config,
+    ) -> eyre::Result<Data> {
+        let output = Command::new(&config.image.aseprite.bin_path)
+            // batch, do not start UI
+            .arg("-b")
+            .args(layer_args)
+            .args(&[
+                // .ase/.aseprite file path
+                source_file_path.as_os_str(),
+                // save .json data as
+                OsStr::new("--data"),
+                data_path.as_os_str(),
+                // json format (hash or array)
+                OsStr::new("--format"),
+                OsStr::new("json-array"),
+                // show tags data
+                OsStr::new("--list-tags"),
+                // show slices data
+                OsStr::new("--list-slices"),
+                // show layers data
+                OsStr::new("--list-layers"),
+                // trim empty space
+                OsStr::new("--trim"),
+            ])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::ExternalProgramFail(output.stderr).into());
+        }
+
+        Data::from_file(data_path)
+    }
+
     fn find_graphic_sources(
         &self,
         images_folder_path: &Path,
@@ -217,13 +260,47 @@ impl FormatProcessor for CommandProcessor {
         output_dir_path: &Path,
         config: &Config,
     ) -> eyre::Result<Graphic> {
+        // generate data
+        let data_pathbuf = output_dir_path.join(DATA_FILE_NAME);
+
+        traceln!(
+            entry: decorator::Entry::None,
+            "  Data filepath: {}",
+            data_pathbuf.display().to_string().bold()
+        );
+
+        let mut aseprite_data = self.export_data(source_file_path, &data_pathbuf, &[], config)?;
+
+        // hitbox layers are extracted as regions, so they shouldn't be at graphic
+        let hitbox_layers: Vec<String> = aseprite_data
+            .meta
+            .hitbox_layers()
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let ignore_hitbox_layers: Vec<&OsStr> = hitbox_layers
+            .iter()
+            .flat_map(|name| [OsStr::new("--ignore-layer"), OsStr::new(name)])
+            .collect();
+
+        if !hitbox_layers.is_empty() {
+            aseprite_data = self.export_data(
+                source_file_path,
+                &data_pathbuf,
+                &ignore_hitbox_layers,
+                config,
+            )?;
+        }
+
         // extract every frame (excluding empty ones)
         let output = Command::new(&config.image.aseprite.bin_path)
+            // batch, do not start UI
+            .arg("-b")
+            // skip empty frames
+            .arg("--ignore-empty")
+            .args(&ignore_hitbox_layers)
             .args(&[
-                // batch, do not start UI
-                OsStr::new("-b"),
-                // skip empty frames
-                OsStr::new("--ignore-empty"),
                 // trim empty space
                 //OsStr::new("--trim"), // removed since it doesn't work at all when exporting images
                 // .ase/.aseprite file path
@@ -238,42 +315,20 @@ impl FormatProcessor for CommandProcessor {
             return Err(Error::ExternalProgramFail(output.stderr).into());
         }
 
-        // generate data
-        let data_pathbuf = output_dir_path.join(DATA_FILE_NAME);
+        // every hitbox layer trimmed region, at each frame
+        let mut layers_frames = Vec::with_capacity(hitbox_layers.len());
 
-        traceln!(
-            entry: decorator::Entry::None,
-            "  Data filepath: {}",
-            data_pathbuf.display().to_string().bold()
-        );
+        for (index, layer) in hitbox_layers.iter().enumerate() {
+            let layer_data = self.export_data(
+                source_file_path,
+                &output_dir_path.join(format!("{}{}.json", HITBOX_DATA_FILE_PREFIX, index)),
+                &[OsStr::new("--layer"), OsStr::new(layer)],
+                config,
+            )?;
 
-        let output = Command::new(&config.image.aseprite.bin_path)
-            .args(&[
-                // batch, do not start UI
-                OsStr::new("-b"),
-                // .ase/.aseprite file path
-                source_file_path.as_os_str(),
-                // save .json data as
-                OsStr::new("--data"),
-                data_pathbuf.as_os_str(),
-                // json format (hash or array)
-                OsStr::new("--format"),
-                OsStr::new("json-array"),
-                // show tags data
-                OsStr::new("--list-tags"),
-                // trim empty space
-                OsStr::new("--trim"),
-            ])
-            .output()
-            .unwrap();
-
-        if !output.status.success() {
-            return Err(Error::ExternalProgramFail(output.stderr).into());
+            layers_frames.push((&layer[HITBOX_PREFIX.len()..], layer_data.frames));
         }
 
-        // process generated images and data
-        let aseprite_data = Data::from_file(&data_pathbuf).map_err(eyre::Error::from)?;
-
         // retrieve source images
         let mut graphic_sources_set =
             self.find_graphic_sources(output_dir_path, &aseprite_data.frames);
@@ -282,6 +337,26 @@ impl FormatProcessor for CommandProcessor {
             return Ok(Graphic::Empty);
         }
 
+        for source_data in graphic_sources_set.sources.iter_mut() {
+            let frame_index = source_data.frame_index;
+            let source = &mut source_data.source;
+
+            source.pivot = aseprite_data.meta.pivot(frame_index);
+
+            for (name, frames) in &layers_frames {
+                if let Some(hitbox) = frames
+                    .get(frame_index as usize)
+                    .and_then(|f| f.sprite_source_size.to_rectangle())
+                {
+                    source.hitboxes.insert(name.to_string(), hitbox);
+                }
+            }
+
+            for (name, hitbox) in aseprite_data.meta.slice_hitboxes(frame_index) {
+                source.hitboxes.insert(name.to_owned(), hitbox);
+            }
+        }
+
         if graphic_sources_set.sources.len() == 1
             && aseprite_data.meta.frame_tags.is_empty()
             && aseprite_data.meta.slices.is_empty()
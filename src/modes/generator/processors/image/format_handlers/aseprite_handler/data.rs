@@ -2,7 +2,12 @@ use std::{fs::OpenOptions, io::BufReader, path::Path};
 
 use serde::Deserialize;
 
-use crate::modes::generator::processors::image::format_handlers::FormatHandlerError;
+use crate::{
+    math::{Point, Rectangle},
+    modes::generator::processors::image::format_handlers::FormatHandlerError,
+};
+
+use super::{HITBOX_PREFIX, PIVOT_SLICE_NAME};
 
 #[derive(Deserialize)]
 pub struct Data {
@@ -22,6 +27,14 @@ pub struct MetaData {
 
     #[serde(default)]
     pub slices: Vec<SliceData>,
+
+    #[serde(default)]
+    pub layers: Vec<LayerData>,
+}
+
+#[derive(Deserialize)]
+pub struct LayerData {
+    pub name: String,
 }
 
 #[derive(Deserialize)]
@@ -41,7 +54,7 @@ pub struct SliceKeyData {
     pub bounds: BoundsData,
 
     #[serde(default)]
-    pub pivot: PositionData,
+    pub pivot: Option<PositionData>,
 }
 
 #[derive(Deserialize)]
@@ -84,6 +97,67 @@ pub struct SizeData {
     pub h: i16,
 }
 
+impl MetaData {
+    /// Pivot point at a frame, defined by it's slice key bounds origin offset by key pivot.
+    pub fn pivot(&self, frame_index: u32) -> Option<Point<i32>> {
+        let key = self
+            .slices
+            .iter()
+            .find(|s| s.name == PIVOT_SLICE_NAME)?
+            .key(frame_index)?;
+
+        let (x, y) = match &key.pivot {
+            Some(pivot) => (pivot.x, pivot.y),
+            None => (0, 0),
+        };
+
+        Some(Point::new(
+            key.bounds.x as i32 + x as i32,
+            key.bounds.y as i32 + y as i32,
+        ))
+    }
+
+    /// Hitbox layers names, including their prefix.
+    pub fn hitbox_layers(&self) -> Vec<&str> {
+        self.layers
+            .iter()
+            .map(|l| l.name.as_str())
+            .filter(|name| name.starts_with(HITBOX_PREFIX))
+            .collect()
+    }
+
+    /// Hitbox slices bounds at a frame, by their name without prefix.
+    pub fn slice_hitboxes(&self, frame_index: u32) -> Vec<(&str, Rectangle<u32>)> {
+        self.slices
+            .iter()
+            .filter_map(|slice| {
+                let name = slice.name.strip_prefix(HITBOX_PREFIX)?;
+                let bounds = &slice.key(frame_index)?.bounds;
+
+                Some((name, bounds.to_rectangle()?))
+            })
+            .collect()
+    }
+}
+
+impl SliceData {
+    /// Key which applies at a frame, a key applies from it's frame until next one.
+    pub fn key(&self, frame_index: u32) -> Option<&SliceKeyData> {
+        super::slice_key(&self.keys, frame_index, |k| u32::try_from(k.frame).ok())
+    }
+}
+
+impl BoundsData {
+    /// Bounds as a rectangle, if it isn't empty and doesn't have negative values.
+    pub fn to_rectangle(&self) -> Option<Rectangle<u32>> {
+        if self.w <= 0 || self.h <= 0 {
+            return None;
+        }
+
+        Rectangle::with(self.x, self.y, self.w, self.h)
+    }
+}
+
 impl Data {
     pub fn from_file<P: AsRef<Path>>(path: P) -> eyre::Result<Self> {
         let data_file = OpenOptions::new().read(true).open(path).unwrap();
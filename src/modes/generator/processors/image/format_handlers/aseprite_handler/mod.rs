@@ -7,6 +7,20 @@ use command_processor::CommandProcessor;
 pub use format_handler::AsepriteFormatHandler;
 use raw_file_processor::RawFileProcessor;
 
+/// Slice which defines graphic's pivot point.
+const PIVOT_SLICE_NAME: &str = "pivot";
+
+/// Layers and slices named with it are extracted as hitboxes, e.g `#hurtbox`.
+const HITBOX_PREFIX: &str = "#";
+
+/// Slice key which applies at a frame, a key applies from it's frame until next one.
+fn slice_key<K>(keys: &[K], frame_index: u32, key_frame: impl Fn(&K) -> Option<u32>) -> Option<&K> {
+    keys.iter()
+        .filter_map(|k| key_frame(k).filter(|f| *f <= frame_index).map(|f| (f, k)))
+        .max_by_key(|(f, _)| *f)
+        .map(|(_, k)| k)
+}
+
 #[allow(dead_code)]
 pub enum AsepriteProcessor {
     Command,
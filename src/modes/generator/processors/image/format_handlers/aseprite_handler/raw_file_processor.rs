@@ -1,17 +1,26 @@
 use asefile::AsepriteFile;
 use image::{self, buffer::EnumerateRows, Rgba, RgbaImage};
-use std::path::Path;
+use std::{collections::BTreeMap, fs, path::Path};
 
 use crate::{
     graphics::{
         animation::{Animation, Frame, FrameIndicesGroup, Track},
         Graphic, GraphicSource, Image,
     },
-    math::Rectangle,
+    math::{Point, Rectangle},
     modes::generator::processors::{image::format_handlers::FormatProcessor, ConfigStatus},
     settings::Config,
 };
 
+use super::{slice_key, HITBOX_PREFIX, PIVOT_SLICE_NAME};
+
+/// Aseprite file layout, see <https://github.com/aseprite/aseprite/blob/main/docs/ase-file-specs.md>.
+const HEADER_SIZE: usize = 128;
+const FRAME_HEADER_SIZE: usize = 16;
+const CHUNK_HEADER_SIZE: usize = 6;
+const LAYER_CHUNK_TYPE: u16 = 0x2004;
+const LAYER_VISIBLE_FLAG: u16 = 0x1;
+
 #[derive(Default)]
 pub struct RawFileProcessor {}
 
@@ -26,13 +35,24 @@ impl FormatProcessor for RawFileProcessor {
         output_dir_path: &Path,
         _config: &Config,
     ) -> eyre::Result<Graphic> {
-        let ase = AsepriteFile::read_file(source_file_path).map_err(eyre::Report::from)?;
+        let mut file_data = fs::read(source_file_path)?;
+        let ase = AsepriteFile::read(file_data.as_slice()).map_err(eyre::Report::from)?;
+
+        // hitbox layers are hidden from a copy, so asefile composites frames without them
+        // and still honors every layer and cel setting
+        let image_ase = if hide_hitbox_layers(&mut file_data) {
+            Some(AsepriteFile::read(file_data.as_slice()).map_err(eyre::Report::from)?)
+        } else {
+            None
+        };
+
+        let image_ase = image_ase.as_ref().unwrap_or(&ase);
         let frame_count = ase.num_frames();
 
         match frame_count {
             0 => Ok(Graphic::Empty),
             1 => Ok(
-                match create_graphic_source(&ase.frame(0), 0, output_dir_path) {
+                match create_graphic_source(&ase, image_ase, 0, output_dir_path) {
                     Some(graphic_source) => {
                         Image::with_graphic_source(graphic_source, source_file_path.to_owned())
                             .unwrap()
@@ -47,14 +67,12 @@ impl FormatProcessor for RawFileProcessor {
 
                 // frames
                 for frame_index in 0..frame_count {
-                    let frame = ase.frame(frame_index);
-
                     if let Some(graphic_source) =
-                        create_graphic_source(&frame, frame_index, output_dir_path)
+                        create_graphic_source(&ase, image_ase, frame_index, output_dir_path)
                     {
                         animation.push_frame(Frame::Contents {
                             graphic_source,
-                            duration: frame.duration(),
+                            duration: ase.frame(frame_index).duration(),
                         });
                     } else {
                         animation.push_frame(Frame::Empty);
@@ -79,30 +97,24 @@ impl FormatProcessor for RawFileProcessor {
     }
 }
 
+/// Graphic source at a frame, its image comes from `image_ase` and everything else from `ase`.
 fn create_graphic_source(
-    frame: &asefile::Frame,
+    ase: &AsepriteFile,
+    image_ase: &AsepriteFile,
     frame_index: u32,
     output_dir_path: &Path,
 ) -> Option<GraphicSource> {
-    let w;
-    let h;
-
-    let frame_image_buffer = {
-        let ase_frame_image = frame.image();
-        let dim = ase_frame_image.dimensions();
-        w = dim.0;
-        h = dim.1;
-
-        // recreate asefile frame image from raw
-        // just to be independent from their image version
-        RgbaImage::from_raw(w, h, ase_frame_image.into_raw()).unwrap()
-    };
+    let ase_frame_image = image_ase.frame(frame_index).image();
+    let (w, h) = ase_frame_image.dimensions();
 
     // ensure w and h isn't zero
     if w == 0 || h == 0 {
         return None;
     }
 
+    // recreate asefile frame image from raw
+    // just to be independent from their image version
+    let frame_image_buffer = RgbaImage::from_raw(w, h, ase_frame_image.into_raw()).unwrap();
     let source = crop_empty_space(frame_image_buffer.enumerate_rows());
 
     if source.is_empty() {
@@ -110,7 +122,129 @@ fn create_graphic_source(
     }
 
     export_graphic(output_dir_path, frame_index, &frame_image_buffer).unwrap();
-    Some(GraphicSource::new(frame_image_buffer, source))
+
+    let mut graphic_source = GraphicSource::new(frame_image_buffer, source);
+    graphic_source.pivot = slice_pivot(ase, frame_index);
+    graphic_source.hitboxes = hitboxes(ase, frame_index);
+
+    Some(graphic_source)
+}
+
+/// Clears visible flag of every hitbox layer at an aseprite file data.
+/// Returns whether any layer was hidden.
+fn hide_hitbox_layers(data: &mut [u8]) -> bool {
+    let mut hidden = false;
+    let frame_count = read_u16(data, 6).unwrap_or(0);
+    let mut frame_start = HEADER_SIZE;
+
+    for _ in 0..frame_count {
+        let (frame_size, chunk_count) = match (
+            read_u32(data, frame_start),
+            read_u16(data, frame_start + 6),
+            read_u32(data, frame_start + 12),
+        ) {
+            // older files only have the 16 bits chunk count
+            (Some(size), Some(old_count), Some(0)) => (size as usize, old_count as u32),
+            (Some(size), Some(_), Some(count)) => (size as usize, count),
+            _ => break,
+        };
+
+        let mut chunk_start = frame_start + FRAME_HEADER_SIZE;
+
+        for _ in 0..chunk_count {
+            let (chunk_size, chunk_type) =
+                match (read_u32(data, chunk_start), read_u16(data, chunk_start + 4)) {
+                    (Some(size), Some(chunk_type)) => (size as usize, chunk_type),
+                    _ => break,
+                };
+
+            if chunk_type == LAYER_CHUNK_TYPE {
+                // flags, type, child level, default width and height, blend mode,
+                // opacity and 3 reserved bytes, then name length and name
+                let layer_start = chunk_start + CHUNK_HEADER_SIZE;
+                let name_start = layer_start + 18;
+                let name = read_u16(data, layer_start + 16)
+                    .and_then(|length| data.get(name_start..name_start + length as usize));
+
+                if let (Some(name), Some(flags)) = (name, read_u16(data, layer_start)) {
+                    if name.starts_with(HITBOX_PREFIX.as_bytes()) {
+                        data[layer_start..layer_start + 2]
+                            .copy_from_slice(&(flags & !LAYER_VISIBLE_FLAG).to_le_bytes());
+                        hidden = true;
+                    }
+                }
+            }
+
+            chunk_start += chunk_size.max(CHUNK_HEADER_SIZE);
+        }
+
+        frame_start += frame_size.max(FRAME_HEADER_SIZE);
+    }
+
+    hidden
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Hitbox layers non empty regions and hitbox slices bounds at a frame, by their name without prefix.
+fn hitboxes(ase: &AsepriteFile, frame_index: u32) -> BTreeMap<String, Rectangle<u32>> {
+    let mut hitboxes = BTreeMap::new();
+    let frame = ase.frame(frame_index);
+
+    for layer in ase.layers() {
+        if let Some(name) = layer.name().strip_prefix(HITBOX_PREFIX) {
+            let cel = frame.layer(layer.id());
+
+            if cel.is_empty() {
+                continue;
+            }
+
+            let cel_image = cel.image();
+            let (width, height) = cel_image.dimensions();
+            let cel_image = RgbaImage::from_raw(width, height, cel_image.into_raw()).unwrap();
+            let region = crop_empty_space(cel_image.enumerate_rows());
+
+            if !region.is_empty() {
+                hitboxes.insert(name.to_owned(), region);
+            }
+        }
+    }
+
+    for slice in ase.slices() {
+        if let Some(name) = slice.name.strip_prefix(HITBOX_PREFIX) {
+            if let Some(key) = slice_key(&slice.keys, frame_index, |k| Some(k.from_frame)) {
+                if let Some(region) = Rectangle::with(
+                    key.origin.0,
+                    key.origin.1,
+                    key.size.0 as i32,
+                    key.size.1 as i32,
+                ) {
+                    hitboxes.insert(name.to_owned(), region);
+                }
+            }
+        }
+    }
+
+    hitboxes
+}
+
+/// Pivot point at a frame, defined by it's slice key origin offset by key pivot.
+fn slice_pivot(ase: &AsepriteFile, frame_index: u32) -> Option<Point<i32>> {
+    let slice = ase.slices().iter().find(|s| s.name == PIVOT_SLICE_NAME)?;
+
+    let key = slice_key(&slice.keys, frame_index, |k| Some(k.from_frame))?;
+
+    let (x, y) = key.pivot.unwrap_or_default();
+
+    Some(Point::new(key.origin.0 + x, key.origin.1 + y))
 }
 
 fn crop_empty_space(rows: EnumerateRows<'_, Rgba<u8>>) -> Rectangle<u32> {
@@ -168,3 +302,73 @@ fn export_graphic(
         image::ImageFormat::Png,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer_chunk(name: &str, flags: u16) -> Vec<u8> {
+        let mut chunk = Vec::new();
+        chunk.extend_from_slice(&((CHUNK_HEADER_SIZE + 18 + name.len()) as u32).to_le_bytes());
+        chunk.extend_from_slice(&LAYER_CHUNK_TYPE.to_le_bytes());
+        chunk.extend_from_slice(&flags.to_le_bytes());
+        chunk.extend_from_slice(&[0; 14]);
+        chunk.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        chunk.extend_from_slice(name.as_bytes());
+        chunk
+    }
+
+    fn file_data(layers: &[(&str, u16)]) -> Vec<u8> {
+        let chunks: Vec<u8> = layers
+            .iter()
+            .flat_map(|(name, flags)| layer_chunk(name, *flags))
+            .collect();
+
+        let mut data = vec![0u8; HEADER_SIZE];
+        data[6..8].copy_from_slice(&1u16.to_le_bytes());
+
+        data.extend_from_slice(&((FRAME_HEADER_SIZE + chunks.len()) as u32).to_le_bytes());
+        data.extend_from_slice(&0xF1FAu16.to_le_bytes());
+        data.extend_from_slice(&(layers.len() as u16).to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&(layers.len() as u32).to_le_bytes());
+        data.extend_from_slice(&chunks);
+        data
+    }
+
+    fn layer_flags(data: &[u8]) -> Vec<u16> {
+        let mut flags = Vec::new();
+        let mut chunk_start = HEADER_SIZE + FRAME_HEADER_SIZE;
+
+        while let Some(size) = read_u32(data, chunk_start) {
+            flags.push(read_u16(data, chunk_start + CHUNK_HEADER_SIZE).unwrap());
+            chunk_start += size as usize;
+        }
+
+        flags
+    }
+
+    #[test]
+    fn only_hitbox_layers_are_hidden() {
+        // visible and editable
+        let mut data = file_data(&[("body", 0x3), ("#hurtbox", 0x3), ("hidden", 0x2)]);
+
+        assert!(hide_hitbox_layers(&mut data));
+        assert_eq!(layer_flags(&data), vec![0x3, 0x2, 0x2]);
+
+        let mut data = file_data(&[("body", 0x3)]);
+
+        assert!(!hide_hitbox_layers(&mut data));
+        assert_eq!(layer_flags(&data), vec![0x3]);
+    }
+
+    #[test]
+    fn truncated_data_is_left_as_is() {
+        let mut data = file_data(&[("#hurtbox", 0x3)]);
+        data.truncate(data.len() - 4);
+        let expected = data.clone();
+
+        assert!(!hide_hitbox_layers(&mut data));
+        assert_eq!(data, expected);
+    }
+}
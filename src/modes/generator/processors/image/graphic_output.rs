@@ -20,4 +20,10 @@ impl GraphicOutput {
     pub fn is_requested(&self) -> bool {
         self.requested
     }
+
+    /// Sort graphics by their source path, so they're arranged regardless of processing order.
+    pub fn sort(&mut self) {
+        self.graphics
+            .sort_by(|a, b| a.source_path().cmp(&b.source_path()));
+    }
 }
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     error,
     fmt::{self, Debug, Display, Formatter},
     io,
@@ -33,6 +34,14 @@ impl GraphicSourceData {
 
         let (source_region, atlas_region) = get_regions(frame_index, frames_data);
 
+        // cached pivots are only the ones provided by source format
+        let (pivot, hitboxes) = match frames_data.get(frame_index as usize) {
+            Some(FrameData::Contents {
+                pivot, hitboxes, ..
+            }) => (pivot.as_ref().map(|p| p.canvas.clone()), hitboxes.clone()),
+            _ => (None, BTreeMap::new()),
+        };
+
         let buffer = {
             let dyn_image = image::open(path).map_err(GraphicSourceDataError::GraphicLoadError)?;
 
@@ -47,6 +56,8 @@ impl GraphicSourceData {
                 atlas_region,
                 buffer,
                 region: source_region,
+                pivot,
+                hitboxes,
             },
             frame_index,
         })
@@ -14,7 +14,7 @@ use tree_decorator::decorator;
 use crate::{
     common::Verbosity,
     modes::generator::processors::{ConfigStatus, Processor, State},
-    settings::{Config, DisplayKind, ProcessorConfig},
+    settings::{CacheValidation, Config, DisplayKind, ProcessorConfig},
     util::{self, Timer},
 };
 
@@ -71,6 +71,11 @@ impl ImageProcessor {
         })
         .unwrap();
 
+        // directory traversal order isn't guaranteed
+        for source_files in source_files_by_extension.values_mut() {
+            source_files.sort();
+        }
+
         source_files_by_extension
     }
 }
@@ -84,6 +89,13 @@ impl Processor for ImageProcessor {
         Some(&config.image)
     }
 
+    fn config_fingerprint(&self, config: &Config) -> Option<u64> {
+        Some(util::hash::fingerprint(&(
+            &config.image.input_path,
+            config.image.processing_hash(),
+        )))
+    }
+
     fn setup(&mut self, state: &mut State) -> ConfigStatus {
         {
             let mut c = state.config.try_write().expect("Can't acquire write lock");
@@ -219,12 +231,24 @@ impl Processor for ImageProcessor {
             if c.is_updated() && !state.graphic_output.is_requested() {
                 let current_cache_metadata = state.create_cache_metadata();
 
-                // check cached and current source directory's modtime
-                if c.meta.generation_metadata().image.source_directory_modtime
-                    == current_cache_metadata
-                        .generation_metadata()
-                        .image
-                        .source_directory_modtime
+                let cached_image_metadata = &c.meta.generation_metadata().image;
+                let current_image_metadata = &current_cache_metadata.generation_metadata().image;
+
+                // check cached and current source files
+                // and if they should be processed differently
+                let same_modtime = match c.validation {
+                    CacheValidation::Modtime => {
+                        cached_image_metadata.source_directory_modtime
+                            == current_image_metadata.source_directory_modtime
+                    }
+                    // modtime changes at every checkout or copy
+                    CacheValidation::Content => true,
+                };
+
+                if same_modtime
+                    && cached_image_metadata.source_files_hash
+                        == current_image_metadata.source_files_hash
+                    && !c.is_stage_outdated(self.name())
                 {
                     infoln!(last, "{}", "Already Updated".green());
                     return;
@@ -282,6 +306,9 @@ impl Processor for ImageProcessor {
             },
         );
 
+        // processing threads may finish at any order
+        state.graphic_output.sort();
+
         if processing.new_files() > 0 || processing.failed_cache_retrieve() > 0 {
             // mark cache as outdated
 
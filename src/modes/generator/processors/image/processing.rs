@@ -344,9 +344,7 @@ impl Processing {
         cache: &mut Cache,
         display_kind: &DisplayKind,
     ) -> Option<Graphic> {
-        let source_metadata = source_filepath.metadata().unwrap();
-
-        match cache.retrieve(&location, &source_metadata) {
+        match cache.retrieve(&location, source_filepath) {
             CacheStatus::Found(cache_entry) => {
                 if let DisplayKind::Detailed = display_kind {
                     infoln!(block, "Cache: {}", "Found".green());
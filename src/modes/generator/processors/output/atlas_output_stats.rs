@@ -3,16 +3,42 @@ use colored::Colorize;
 
 pub struct AtlasOutputStats {
     free_space_percent: f32,
+
+    /// File size (in bytes) before and after optimization.
+    optimized_size: Option<(u64, u64)>,
 }
 
 impl AtlasOutputStats {
     pub fn new(free_space_percent: f32) -> Self {
-        Self { free_space_percent }
+        Self {
+            free_space_percent,
+            optimized_size: None,
+        }
+    }
+
+    pub fn with_optimized_size(mut self, before: u64, after: u64) -> Self {
+        self.optimized_size = Some((before, after));
+        self
     }
 }
 
 impl OutputStats for AtlasOutputStats {
     fn display_stats(&self) {
+        if let Some((before, after)) = self.optimized_size {
+            let reduction_percent = if before > 0 {
+                100.0 * (1.0 - after as f64 / before as f64)
+            } else {
+                0.0
+            };
+
+            infoln!(
+                "Optimized  {} -> {}  ({:.2}% smaller)",
+                format_size(before),
+                format_size(after).bold(),
+                reduction_percent
+            );
+        }
+
         let used_space_percent = (100.0f32 - self.free_space_percent).clamp(0.0, 100.0);
         infoln!(block, "Used space");
 
@@ -31,3 +57,13 @@ impl OutputStats for AtlasOutputStats {
         );
     }
 }
+
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.2} MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.2} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
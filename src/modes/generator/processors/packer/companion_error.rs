@@ -0,0 +1,63 @@
+use std::{
+    error,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+use crate::math::Size;
+
+#[derive(Debug)]
+pub enum CompanionError {
+    KindMismatch,
+    FrameCountMismatch {
+        expected: usize,
+        found: usize,
+    },
+    FrameSizeMismatch {
+        frame: usize,
+        expected: Size<u32>,
+        found: Size<u32>,
+    },
+    UnexpectedFrameContents {
+        frame: usize,
+    },
+}
+
+impl error::Error for CompanionError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self {
+            CompanionError::KindMismatch => None,
+            CompanionError::FrameCountMismatch { .. } => None,
+            CompanionError::FrameSizeMismatch { .. } => None,
+            CompanionError::UnexpectedFrameContents { .. } => None,
+        }
+    }
+}
+
+impl Display for CompanionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self {
+            CompanionError::KindMismatch => {
+                write!(f, "Companion and base graphic aren't of the same kind")
+            }
+            CompanionError::FrameCountMismatch { expected, found } => write!(
+                f,
+                "Frame count mismatch, expected {} but found {}",
+                expected, found
+            ),
+            CompanionError::FrameSizeMismatch {
+                frame,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Frame {} size mismatch, expected {}x{} but found {}x{}",
+                frame, expected.width, expected.height, found.width, found.height
+            ),
+            CompanionError::UnexpectedFrameContents { frame } => write!(
+                f,
+                "Frame {} has contents, but base graphic's frame is empty",
+                frame
+            ),
+        }
+    }
+}
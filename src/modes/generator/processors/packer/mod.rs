@@ -1,10 +1,14 @@
+mod companion_error;
 mod packer_error;
 mod packer_processor;
+mod packing_constraints;
 mod row_tight_packer;
 mod validation_error;
 
+pub use companion_error::CompanionError;
 pub use packer_error::PackerError;
 pub use packer_processor::PackerProcessor;
+pub use packing_constraints::PackingConstraints;
 pub use row_tight_packer::RowTightPacker;
 use validation_error::ValidationError;
 
@@ -15,6 +19,7 @@ pub trait Packer {
     fn execute(
         &self,
         atlas_min_size: Size<u32>,
+        constraints: &PackingConstraints,
         graphic_sources: &mut Vec<&mut GraphicSource>,
     ) -> eyre::Result<f32>;
 }
@@ -1,24 +1,25 @@
 use colored::Colorize;
-use image::{self, GenericImage};
+use image::{self, GenericImage, RgbaImage};
 use std::{
-    fs, io, iter,
+    collections::HashMap,
+    fs, io, iter, mem,
     path::{Path, PathBuf},
 };
 use tree_decorator::decorator;
 
 use crate::{
     common::Verbosity,
-    graphics::{animation::Frame, Graphic, GraphicSource},
+    graphics::{animation::Frame, filters, texture, Graphic, GraphicSource},
     math::{self, Size},
     modes::generator::processors::{
         output::{self, AtlasOutputStats, OutputFile},
         ConfigStatus, Processor, State,
     },
-    settings::{Config, ProcessorConfig},
-    util::Timer,
+    settings::{Config, ImageFormat, OutputConfig, ProcessorConfig},
+    util::{self, Timer},
 };
 
-use super::{Packer, PackerError, ValidationError};
+use super::{CompanionError, Packer, PackerError, PackingConstraints, ValidationError};
 
 const DEFAULT_MAX_ATLAS_SIZE: u32 = 4096;
 
@@ -42,23 +43,84 @@ impl<P: Packer> PackerProcessor<P> {
             return Err(ValidationError::CacheNotUpdated.into());
         }
 
+        if cache.is_stage_outdated(self.name()) {
+            return Err(ValidationError::OutputSettingsMismatch.into());
+        }
+
         let config = state.config.try_read().expect("Can't retrieve a read lock");
-        let output_filepath = self.output_file_path(&config);
 
+        for (companion, scale) in self.pages(&config) {
+            for format in &config.output.image_formats {
+                let output_filepath = self.output_file_path(&config, companion, scale, *format);
+
+                // compressed formats can't have it's dimensions checked
+                if !format.is_compressed() {
+                    self.validate_image_dimensions(
+                        &output_filepath,
+                        state.output.atlas_width * scale,
+                        state.output.atlas_height * scale,
+                    )?;
+                }
+
+                let output_file =
+                    OutputFile::with_stats(output_filepath, AtlasOutputStats::new(0.0));
+
+                if let Err(e) = state.output.register_file(output_file) {
+                    match e.downcast_ref::<output::Error>().unwrap() {
+                        output::Error::FileExpected => {
+                            infoln!("Output file not found");
+                            return Err(ValidationError::AtlasImageNotFound.into());
+                        }
+                        _ => panic!("{}", e),
+                    }
+                }
+
+                // compressed formats stores mipmaps at the same file
+                if format.is_compressed() {
+                    continue;
+                }
+
+                for level in 1..=config.packer.mipmap_levels() {
+                    let mipmap_file = OutputFile::new(
+                        self.mipmap_file_path(&config, companion, scale, *format, level),
+                    );
+
+                    if let Err(e) = state.output.register_file(mipmap_file) {
+                        match e.downcast_ref::<output::Error>().unwrap() {
+                            output::Error::FileExpected => {
+                                infoln!("Mipmap file not found");
+                                return Err(ValidationError::AtlasImageNotFound.into());
+                            }
+                            _ => panic!("{}", e),
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_image_dimensions(
+        &self,
+        output_filepath: &Path,
+        expected_width: u32,
+        expected_height: u32,
+    ) -> eyre::Result<()> {
         match output_filepath.metadata() {
             Ok(m) => {
                 if m.is_file() {
                     // check image data
-                    let (w, h) = image::image_dimensions(&output_filepath)
+                    let (w, h) = image::image_dimensions(output_filepath)
                         .map_err(ValidationError::AtlasImageLoadFailed)?;
 
-                    if w != state.output.atlas_width || h != state.output.atlas_height {
+                    if w != expected_width || h != expected_height {
                         traceln!(
                             "Previous output file image size {}x{} differs from current size {}x{}",
                             w,
                             h,
-                            state.output.atlas_width,
-                            state.output.atlas_height,
+                            expected_width,
+                            expected_height,
                         );
 
                         return Err(ValidationError::PreviousFileImageSizeMismatch.into());
@@ -71,37 +133,33 @@ impl<P: Packer> PackerProcessor<P> {
             },
         }
 
-        let output_file = OutputFile::with_stats(output_filepath, AtlasOutputStats::new(0.0));
-
-        if let Err(e) = state.output.register_file(output_file) {
-            match e.downcast_ref::<output::Error>().unwrap() {
-                output::Error::FileExpected => {
-                    infoln!("Output file not found");
-                    return Err(ValidationError::AtlasImageNotFound.into());
-                }
-                _ => panic!("{}", e),
-            }
-        }
-
         Ok(())
     }
 
     fn generate_image(
         &self,
-        output_path: &Path,
         width: u32,
         height: u32,
+        scale: u32,
         graphic_sources: &[&mut GraphicSource],
-    ) -> eyre::Result<()> {
-        let mut image_buffer = image::ImageBuffer::from_pixel(width, height, image::Rgba([0u8; 4]));
+        output_config: &OutputConfig,
+    ) -> eyre::Result<RgbaImage> {
+        let mut image_buffer =
+            image::ImageBuffer::from_pixel(width * scale, height * scale, image::Rgba([0u8; 4]));
 
         for graphic_source in graphic_sources {
             match &graphic_source.atlas_region {
                 Some(atlas_region) => {
+                    let region_buffer = if scale == 1 {
+                        graphic_source.region_buffer_view().to_image()
+                    } else {
+                        graphic_source.scaled_region_buffer(scale)
+                    };
+
                     image_buffer.copy_from(
-                        &graphic_source.region_buffer_view().to_image(),
-                        atlas_region.x,
-                        atlas_region.y,
+                        &region_buffer,
+                        atlas_region.x * scale,
+                        atlas_region.y * scale,
                     )?;
                 }
                 None => {
@@ -110,16 +168,204 @@ impl<P: Packer> PackerProcessor<P> {
             }
         }
 
-        image_buffer
-            .save_with_format(output_path, image::ImageFormat::Png)
-            .map_err(eyre::Error::from)
+        if output_config.alpha_bleed {
+            filters::alpha_bleed(&mut image_buffer);
+        }
+
+        Ok(image_buffer)
+    }
+
+    /// Writes an uncompressed image format file.
+    /// Returns file size before and after optimization, when it's optimized.
+    fn save_image(
+        &self,
+        image_buffer: &RgbaImage,
+        output_path: &Path,
+        format: ImageFormat,
+        output_config: &OutputConfig,
+        premultiply_alpha: bool,
+    ) -> eyre::Result<Option<(u64, u64)>> {
+        let image_format = format
+            .lossless_format()
+            .unwrap_or_else(|| panic!("{} isn't an uncompressed image format", format));
+
+        let premultiplied_buffer;
+        let image_buffer = if premultiply_alpha {
+            let mut buffer = image_buffer.clone();
+            filters::premultiply_alpha(&mut buffer);
+            premultiplied_buffer = buffer;
+            &premultiplied_buffer
+        } else {
+            image_buffer
+        };
+
+        if format != ImageFormat::Png || output_config.png_optimization == 0 {
+            image_buffer.save_with_format(output_path, image_format)?;
+            return Ok(None);
+        }
+
+        let mut encoded = io::Cursor::new(Vec::new());
+        image_buffer.write_to(&mut encoded, image::ImageOutputFormat::Png)?;
+        let encoded = encoded.into_inner();
+
+        let options = oxipng::Options::from_preset(output_config.png_optimization.min(6));
+        let optimized = oxipng::optimize_from_memory(&encoded, &options)?;
+        fs::write(output_path, &optimized)?;
+
+        Ok(Some((encoded.len() as u64, optimized.len() as u64)))
+    }
+
+    /// Encodes every level (largest first) and writes them into a compressed image format file.
+    fn save_compressed(
+        &self,
+        levels: &[RgbaImage],
+        output_path: &Path,
+        format: ImageFormat,
+        output_config: &OutputConfig,
+        premultiply_alpha: bool,
+    ) -> eyre::Result<()> {
+        let encoded_levels = levels
+            .iter()
+            .map(|level| {
+                if premultiply_alpha {
+                    let mut premultiplied_level = level.clone();
+                    filters::premultiply_alpha(&mut premultiplied_level);
+                    texture::encode(&premultiplied_level, output_config.compression)
+                } else {
+                    texture::encode(level, output_config.compression)
+                }
+            })
+            .collect::<Vec<Vec<u8>>>();
+
+        let (width, height) = levels[0].dimensions();
+
+        match format {
+            ImageFormat::Dds => texture::dds::save(
+                output_path,
+                width,
+                height,
+                &encoded_levels,
+                output_config.compression,
+                premultiply_alpha,
+            ),
+            ImageFormat::Ktx2 => texture::ktx2::save(
+                output_path,
+                width,
+                height,
+                &encoded_levels,
+                output_config.compression,
+                premultiply_alpha,
+            ),
+            _ => panic!("{} isn't a compressed image format", format),
+        }
+        .map_err(eyre::Error::from)
+    }
+
+    fn output_file_path(
+        &self,
+        config: &Config,
+        companion: &str,
+        scale: u32,
+        format: ImageFormat,
+    ) -> PathBuf {
+        config.cache.atlas_path().join(format!(
+            "{}.{}",
+            config.output.page_name(companion, scale),
+            format.extension()
+        ))
     }
 
-    fn output_file_path(&self, config: &Config) -> PathBuf {
-        config
-            .cache
-            .atlas_path()
-            .join(format!("{}.png", config.output.name_or_default()))
+    fn mipmap_file_path(
+        &self,
+        config: &Config,
+        companion: &str,
+        scale: u32,
+        format: ImageFormat,
+        level: u32,
+    ) -> PathBuf {
+        config.cache.atlas_path().join(format!(
+            "{}.mip{}.{}",
+            config.output.page_name(companion, scale),
+            level,
+            format.extension()
+        ))
+    }
+
+    /// Every atlas page, as (companion suffix, scale), base pages uses an empty suffix.
+    fn pages<'c>(&self, config: &'c Config) -> Vec<(&'c str, u32)> {
+        let scales = config.image.scale.factors();
+
+        iter::once("")
+            .chain(
+                config
+                    .image
+                    .companions
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|suffix| !suffix.is_empty()),
+            )
+            .flat_map(|companion| scales.iter().map(move |scale| (companion, *scale)))
+            .collect()
+    }
+
+    /// Finds, to every graphic, it's base graphic index and companion suffix, if it's a companion.
+    fn find_companions<'c>(
+        &self,
+        config: &'c Config,
+        graphics: &[Graphic],
+    ) -> Vec<Option<(usize, &'c str)>> {
+        let locations = graphics
+            .iter()
+            .enumerate()
+            .filter_map(|(index, g)| Some((g.source_path()?.with_extension(""), index)))
+            .collect::<HashMap<PathBuf, usize>>();
+
+        graphics
+            .iter()
+            .map(|g| {
+                let (base_path, suffix) = config
+                    .image
+                    .companion_base(&g.source_path()?.with_extension(""))?;
+
+                locations
+                    .get(&base_path)
+                    .map(|base_index| (*base_index, suffix))
+            })
+            .collect()
+    }
+
+    /// Places every companion graphic at the same atlas regions as it's base graphic.
+    fn align_companions(&self, graphics: &mut [Graphic], companions: &[Option<(usize, &str)>]) {
+        let mut mismatches = 0;
+
+        for (index, companion) in companions.iter().enumerate() {
+            let base_index = match companion {
+                Some((base_index, _)) => *base_index,
+                None => continue,
+            };
+
+            let mut companion_graphic = mem::replace(&mut graphics[index], Graphic::Empty);
+
+            if let Err(e) = align_companion(&graphics[base_index], &mut companion_graphic) {
+                errorln!(
+                    "Companion '{}' doesn't match base '{}': {}",
+                    companion_graphic.source_path().unwrap().display(),
+                    graphics[base_index].source_path().unwrap().display(),
+                    e
+                );
+
+                mismatches += 1;
+            }
+
+            graphics[index] = companion_graphic;
+        }
+
+        if mismatches > 0 {
+            panic!(
+                "{} companion graphic(s) doesn't match their base graphic",
+                mismatches
+            );
+        }
     }
 }
 
@@ -132,6 +378,27 @@ impl<P: Packer> Processor for PackerProcessor<P> {
         Some(&config.packer)
     }
 
+    fn config_fingerprint(&self, config: &Config) -> Option<u64> {
+        Some(util::hash::fingerprint(&(
+            (
+                config.packer.atlas_size,
+                config.packer.optimize,
+                config.packer.alignment,
+                config.packer.mipmaps,
+                &config.packer.retry,
+            ),
+            (
+                &config.output.name,
+                config.output.premultiply_alpha,
+                config.output.alpha_bleed,
+                &config.output.image_formats,
+                config.output.compression,
+                config.output.png_optimization,
+            ),
+            (&config.image.companions, &config.image.scale),
+        )))
+    }
+
     fn setup(&mut self, state: &mut State) -> ConfigStatus {
         let mut config_status = ConfigStatus::NotModified;
         let mut c = state
@@ -170,32 +437,72 @@ impl<P: Packer> Processor for PackerProcessor<P> {
             traceln!("Using default atlas size {}x{0}", c.packer.atlas_size);
         }
 
+        if c.output.image_formats.is_empty() {
+            panic!("At least one output image format should be provided");
+        }
+
+        if c.output.image_formats.contains(&ImageFormat::Dds)
+            && !texture::dds::supports(c.output.compression)
+        {
+            panic!(
+                "Output image format dds doesn't supports {} compression",
+                c.output.compression
+            );
+        }
+
         if state.args().global.force {
             state.graphic_output.request();
         } else {
-            let output_filepath = self.output_file_path(&c);
-
-            // check if will need to regenerate output file
-            // and ensure graphic output will be available at execute step
-            if output_filepath.is_file() {
-                // check if output file differs from requested dimensions
-                let (w, h) = image::image_dimensions(&output_filepath).unwrap_or_else(|_| {
-                    panic!("Can't read output image at '{}'", output_filepath.display())
-                });
+            for (companion, scale) in self.pages(&c) {
+                for format in &c.output.image_formats {
+                    let output_filepath = self.output_file_path(&c, companion, scale, *format);
+
+                    // check if will need to regenerate output file
+                    // and ensure graphic output will be available at execute step
+                    if output_filepath.is_file() {
+                        if format.is_compressed() {
+                            continue;
+                        }
+
+                        // check if output file differs from requested dimensions
+                        let (w, h) =
+                            image::image_dimensions(&output_filepath).unwrap_or_else(|_| {
+                                panic!("Can't read output image at '{}'", output_filepath.display())
+                            });
+
+                        if state.output.atlas_width * scale != w
+                            || state.output.atlas_height * scale != h
+                        {
+                            state.graphic_output.request();
+                        }
 
-                if state.output.atlas_width != w || state.output.atlas_height != h {
-                    state.graphic_output.request();
+                        for level in 1..=c.packer.mipmap_levels() {
+                            if !self
+                                .mipmap_file_path(&c, companion, scale, *format, level)
+                                .is_file()
+                            {
+                                state.graphic_output.request();
+                            }
+                        }
+                    } else if output_filepath.exists() {
+                        panic!(
+                            "Output file path '{}' is already in use",
+                            output_filepath.display()
+                        )
+                    } else {
+                        state.graphic_output.request();
+                    }
                 }
-            } else if output_filepath.exists() {
-                panic!(
-                    "Output file path '{}' is already in use",
-                    output_filepath.display()
-                )
-            } else {
-                state.graphic_output.request();
             }
         }
 
+        if c.packer.mipmaps && c.packer.mipmap_levels() == 0 {
+            warnln!(
+                "Mipmaps requires packer alignment to be a multiple of 2, current alignment is {}",
+                c.packer.alignment
+            );
+        }
+
         doneln!();
 
         config_status
@@ -219,7 +526,8 @@ impl<P: Packer> Processor for PackerProcessor<P> {
                 Err(e) => match e.downcast_ref::<ValidationError>().unwrap() {
                     ValidationError::CacheNotUpdated
                     | ValidationError::AtlasImageNotFound
-                    | ValidationError::PreviousFileImageSizeMismatch => (),
+                    | ValidationError::PreviousFileImageSizeMismatch
+                    | ValidationError::OutputSettingsMismatch => (),
                     _ => Err(e).unwrap(),
                 },
             }
@@ -235,41 +543,21 @@ impl<P: Packer> Processor for PackerProcessor<P> {
             state.output.atlas_height,
         );
 
+        let c = state.config.try_read().expect("Can't retrieve a read lock");
+
+        // companion graphics shares it's base graphic layout, so they aren't packed
+        let companions = self.find_companions(&c, &state.graphic_output.graphics);
+
         let mut graphic_sources = state
             .graphic_output
             .graphics
             .iter_mut()
-            .filter_map(
-                |g| -> Option<Box<dyn Iterator<Item = &mut GraphicSource>>> {
-                    match g {
-                        Graphic::Image(img) => {
-                            if !img.graphic_source.region.is_empty() {
-                                Some(Box::new(iter::once(&mut img.graphic_source)))
-                            } else {
-                                None
-                            }
-                        }
-                        Graphic::Animation(anim) => {
-                            Some(Box::new(anim.frames.iter_mut().filter_map(|f| match f {
-                                Frame::Empty => None,
-                                Frame::Contents { graphic_source, .. } => {
-                                    if !graphic_source.region.is_empty() {
-                                        Some(graphic_source)
-                                    } else {
-                                        None
-                                    }
-                                }
-                            })))
-                        }
-                        Graphic::Empty => None,
-                    }
-                },
-            )
-            .flatten()
+            .zip(companions.iter())
+            .filter(|(_, companion)| companion.is_none())
+            .flat_map(|(g, _)| packable_sources(g))
             .collect::<Vec<&mut GraphicSource>>();
 
         infoln!("Using {} packer", self.packer.name().bold());
-        let c = state.config.try_read().expect("Can't retrieve a read lock");
 
         let until_atlas_size = if c.packer.retry.until_atlas_size == 0 {
             DEFAULT_MAX_ATLAS_SIZE
@@ -277,10 +565,21 @@ impl<P: Packer> Processor for PackerProcessor<P> {
             c.packer.retry.until_atlas_size
         };
 
+        let constraints = PackingConstraints::new(c.packer.alignment);
+
+        if constraints.alignment > 1 {
+            traceln!(
+                entry: decorator::Entry::None,
+                "With alignment {}",
+                constraints.alignment
+            );
+        }
+
         let mut retries = 0;
         let usage = loop {
             match self.packer.execute(
                 Size::new(state.output.atlas_width, state.output.atlas_height),
+                &constraints,
                 &mut graphic_sources,
             ) {
                 Ok(u) => break u,
@@ -333,6 +632,18 @@ impl<P: Packer> Processor for PackerProcessor<P> {
         };
 
         infoln!(last, "{}", "Done".green());
+
+        // atlas regions may have changed, data should be regenerated as well
+        if let Some(cache) = state.cache.as_mut() {
+            cache.mark_as_outdated();
+        }
+
+        if companions.iter().any(Option::is_some) {
+            infoln!("Aligning companion graphics");
+            drop(graphic_sources);
+            self.align_companions(&mut state.graphic_output.graphics, &companions);
+        }
+
         infoln!("Generating output");
 
         let atlas_dir_path = c.cache.atlas_path();
@@ -361,30 +672,212 @@ impl<P: Packer> Processor for PackerProcessor<P> {
             },
         }
 
-        // generate atlas file at cache output path
-        let cache_output_path = self.output_file_path(&c);
+        // generate an atlas file, at cache output path, to every page
+        let pages = self.pages(&c);
+        let mut page_companion = None;
+        let mut page_sources = Vec::new();
+
+        for (companion, scale) in pages {
+            if page_companion != Some(companion) {
+                page_companion = Some(companion);
+                page_sources = state
+                    .graphic_output
+                    .graphics
+                    .iter_mut()
+                    .zip(companions.iter())
+                    .filter(|(_, graphic_companion)| match graphic_companion {
+                        Some((_, suffix)) => *suffix == companion,
+                        None => companion.is_empty(),
+                    })
+                    .flat_map(|(g, _)| packable_sources(g))
+                    .collect::<Vec<&mut GraphicSource>>();
+            }
 
-        infoln!(
-            "Exporting to file {}",
-            cache_output_path.display().to_string().bold()
-        );
+            let image_buffer = self
+                .generate_image(
+                    state.output.atlas_width,
+                    state.output.atlas_height,
+                    scale,
+                    &page_sources,
+                    &c.output,
+                )
+                .unwrap();
 
-        self.generate_image(
-            &cache_output_path,
-            state.output.atlas_width,
-            state.output.atlas_height,
-            &graphic_sources,
-        )
-        .unwrap();
+            // mipmaps are downsampled from previous level, before premultiplying it
+            let mut levels = vec![image_buffer];
+
+            for _ in 1..=c.packer.mipmap_levels() {
+                let mipmap_buffer = filters::downsample(levels.last().unwrap());
+                levels.push(mipmap_buffer);
+            }
+
+            // companion pages, e.g normal maps, holds values which can't be scaled by alpha
+            let premultiply_alpha = c.output.premultiply_alpha && companion.is_empty();
+
+            for format in &c.output.image_formats {
+                let cache_output_path = self.output_file_path(&c, companion, scale, *format);
+
+                infoln!(
+                    "Exporting to file {}",
+                    cache_output_path.display().to_string().bold()
+                );
+
+                let mut optimized_size = None;
+
+                if format.is_compressed() {
+                    traceln!("With {} compression", c.output.compression);
+
+                    self.save_compressed(
+                        &levels,
+                        &cache_output_path,
+                        *format,
+                        &c.output,
+                        premultiply_alpha,
+                    )
+                    .unwrap();
+                } else {
+                    optimized_size = self
+                        .save_image(
+                            &levels[0],
+                            &cache_output_path,
+                            *format,
+                            &c.output,
+                            premultiply_alpha,
+                        )
+                        .unwrap();
 
-        // output
-        let output_file = OutputFile::with_stats(cache_output_path, AtlasOutputStats::new(usage));
-        state.output.register_file(output_file).unwrap();
+                    for (level, mipmap_buffer) in levels.iter().enumerate().skip(1) {
+                        let level = level as u32;
+                        let mipmap_path =
+                            self.mipmap_file_path(&c, companion, scale, *format, level);
+
+                        traceln!(
+                            "Exporting mipmap level {} to file {}",
+                            level,
+                            mipmap_path.display().to_string().bold()
+                        );
+
+                        self.save_image(
+                            mipmap_buffer,
+                            &mipmap_path,
+                            *format,
+                            &c.output,
+                            premultiply_alpha,
+                        )
+                        .unwrap();
+
+                        state
+                            .output
+                            .register_file(OutputFile::new(mipmap_path))
+                            .unwrap();
+                    }
+                }
+
+                // output
+                let mut stats = AtlasOutputStats::new(usage);
+
+                if let Some((before, after)) = optimized_size {
+                    traceln!("Optimized from {} to {} bytes", before, after);
+                    stats = stats.with_optimized_size(before, after);
+                }
+
+                let output_file = OutputFile::with_stats(cache_output_path, stats);
+                state.output.register_file(output_file).unwrap();
+            }
+        }
 
         doneln_with_timer!(timer);
     }
 }
 
+/// Graphic sources, from a graphic, which should be placed at atlas.
+fn packable_sources(graphic: &mut Graphic) -> Box<dyn Iterator<Item = &mut GraphicSource> + '_> {
+    match graphic {
+        Graphic::Image(img) => {
+            if !img.graphic_source.region.is_empty() {
+                Box::new(iter::once(&mut img.graphic_source))
+            } else {
+                Box::new(iter::empty())
+            }
+        }
+        Graphic::Animation(anim) => Box::new(anim.frames.iter_mut().filter_map(|f| match f {
+            Frame::Empty => None,
+            Frame::Contents { graphic_source, .. } => {
+                if !graphic_source.region.is_empty() {
+                    Some(graphic_source)
+                } else {
+                    None
+                }
+            }
+        })),
+        Graphic::Empty => Box::new(iter::empty()),
+    }
+}
+
+fn align_companion(base: &Graphic, companion: &mut Graphic) -> Result<(), CompanionError> {
+    match (base, companion) {
+        (_, Graphic::Empty) => Ok(()),
+        (Graphic::Image(base), Graphic::Image(companion)) => {
+            align_companion_source(0, &base.graphic_source, &mut companion.graphic_source)
+        }
+        (Graphic::Animation(base), Graphic::Animation(companion)) => {
+            if base.frames.len() != companion.frames.len() {
+                return Err(CompanionError::FrameCountMismatch {
+                    expected: base.frames.len(),
+                    found: companion.frames.len(),
+                });
+            }
+
+            for (index, (base_frame, companion_frame)) in base
+                .frames
+                .iter()
+                .zip(companion.frames.iter_mut())
+                .enumerate()
+            {
+                match (base_frame, companion_frame) {
+                    (_, Frame::Empty) => (),
+                    (Frame::Empty, Frame::Contents { .. }) => {
+                        return Err(CompanionError::UnexpectedFrameContents { frame: index })
+                    }
+                    (
+                        Frame::Contents {
+                            graphic_source: base_source,
+                            ..
+                        },
+                        Frame::Contents {
+                            graphic_source: companion_source,
+                            ..
+                        },
+                    ) => align_companion_source(index, base_source, companion_source)?,
+                }
+            }
+
+            Ok(())
+        }
+        _ => Err(CompanionError::KindMismatch),
+    }
+}
+
+fn align_companion_source(
+    frame: usize,
+    base: &GraphicSource,
+    companion: &mut GraphicSource,
+) -> Result<(), CompanionError> {
+    if base.buffer.dimensions() != companion.buffer.dimensions() {
+        return Err(CompanionError::FrameSizeMismatch {
+            frame,
+            expected: Size::new(base.buffer.width(), base.buffer.height()),
+            found: Size::new(companion.buffer.width(), companion.buffer.height()),
+        });
+    }
+
+    // companion keeps base clipping region, even if it's own contents are smaller
+    companion.region = base.region.clone();
+    companion.atlas_region = base.atlas_region.clone();
+
+    Ok(())
+}
+
 impl<P: Packer> Verbosity for PackerProcessor<P> {
     fn verbose(&mut self, verbose: bool) {
         self.verbose = verbose;
@@ -394,3 +887,82 @@ impl<P: Packer> Verbosity for PackerProcessor<P> {
         self.verbose
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use image::Rgba;
+
+    use super::*;
+    use crate::{
+        graphics::Image,
+        math::Rectangle,
+        modes::generator::processors::{image::GraphicOutput, packer::RowTightPacker},
+    };
+
+    fn graphic(path: &str, color: [u8; 4], width: u32, height: u32) -> Graphic {
+        Graphic::Image(Image {
+            source_name: Path::new(path).file_stem().unwrap().to_owned(),
+            source_path: PathBuf::from(path),
+            graphic_source: GraphicSource {
+                atlas_region: None,
+                buffer: RgbaImage::from_pixel(width, height, Rgba(color)),
+                region: Rectangle::new(0, 0, width, height),
+                pivot: None,
+                hitboxes: BTreeMap::new(),
+            },
+        })
+    }
+
+    /// Arranges graphics as image processor output and packs them into an atlas image.
+    fn compose(graphics: Vec<Graphic>) -> RgbaImage {
+        let mut graphic_output = GraphicOutput::new();
+        graphic_output.graphics = graphics;
+        graphic_output.sort();
+
+        let mut graphic_sources = graphic_output
+            .graphics
+            .iter_mut()
+            .flat_map(packable_sources)
+            .collect::<Vec<&mut GraphicSource>>();
+
+        let processor = PackerProcessor::new(RowTightPacker::new());
+        processor
+            .packer
+            .execute(
+                Size::new(32, 32),
+                &PackingConstraints::new(1),
+                &mut graphic_sources,
+            )
+            .unwrap();
+
+        processor
+            .generate_image(32, 32, 1, &graphic_sources, &OutputConfig::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn atlas_image_is_independent_of_processing_order() {
+        // tied sizes, only told apart by their contents
+        let graphics = || {
+            vec![
+                graphic("a.png", [255, 0, 0, 255], 8, 8),
+                graphic("b.png", [0, 255, 0, 255], 8, 8),
+                graphic("c.png", [0, 0, 255, 255], 8, 8),
+                graphic("d.png", [255, 255, 0, 255], 16, 4),
+            ]
+        };
+
+        let mut reversed = graphics();
+        reversed.reverse();
+
+        let mut shuffled = graphics();
+        shuffled.swap(0, 2);
+        shuffled.swap(1, 3);
+
+        let expected = compose(graphics());
+        assert_eq!(compose(reversed).as_raw(), expected.as_raw());
+        assert_eq!(compose(shuffled).as_raw(), expected.as_raw());
+    }
+}
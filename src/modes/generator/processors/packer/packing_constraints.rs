@@ -0,0 +1,41 @@
+use crate::math::Size;
+
+/// Constraints every [`Packer`](super::Packer) should honor when placing graphic sources.
+pub struct PackingConstraints {
+    /// Every atlas region position and footprint should be a multiple of it.
+    pub alignment: u32,
+}
+
+impl PackingConstraints {
+    pub fn new(alignment: u32) -> Self {
+        Self {
+            alignment: alignment.max(1),
+        }
+    }
+
+    /// Space taken at atlas by a region of provided size.
+    pub fn footprint(&self, size: &Size<u32>) -> Size<u32> {
+        Size::new(self.align(size.width), self.align(size.height))
+    }
+
+    /// Atlas area which can be used to place aligned regions.
+    pub fn usable_size(&self, atlas_size: &Size<u32>) -> Size<u32> {
+        Size::new(
+            atlas_size.width - atlas_size.width % self.alignment,
+            atlas_size.height - atlas_size.height % self.alignment,
+        )
+    }
+
+    fn align(&self, value: u32) -> u32 {
+        match value % self.alignment {
+            0 => value,
+            remainder => value + self.alignment - remainder,
+        }
+    }
+}
+
+impl Default for PackingConstraints {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
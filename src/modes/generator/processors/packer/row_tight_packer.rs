@@ -3,7 +3,7 @@ use crate::{
     math::{Rectangle, Size},
 };
 
-use super::{Packer, PackerError};
+use super::{Packer, PackerError, PackingConstraints};
 
 pub struct RowTightPacker {}
 
@@ -21,17 +21,21 @@ impl Packer for RowTightPacker {
     fn execute(
         &self,
         atlas_size: Size<u32>,
+        constraints: &PackingConstraints,
         graphic_sources: &mut Vec<&mut GraphicSource>,
     ) -> eyre::Result<f32> {
-        if atlas_size.width == 0 || atlas_size.height == 0 {
+        let usable_size = constraints.usable_size(&atlas_size);
+
+        if usable_size.width == 0 || usable_size.height == 0 {
             return Err(PackerError::EmptyTargetSize.into());
         }
 
         let atlas_area = atlas_size.area();
-        let mut empty_spaces: Vec<Rectangle<u32>> = vec![atlas_size.into()];
+        let mut empty_spaces: Vec<Rectangle<u32>> = vec![usable_size.into()];
 
         // sort by increasing order of their height and width
-        graphic_sources.sort_unstable_by(|a, b| {
+        // stable sort keeps ties at their given order, so layout is reproducible
+        graphic_sources.sort_by(|a, b| {
             (*a).region
                 .height
                 .cmp(&(*b).region.height)
@@ -44,8 +48,10 @@ impl Packer for RowTightPacker {
                 return Err(PackerError::OutOfSpace.into());
             }
 
+            // every footprint is aligned, so every empty space stays aligned as well
+            let size = constraints.footprint(&source.region.size());
+
             let empty_space = {
-                let size = source.region.size();
                 let mut best_fit: Option<SpaceFit> = None;
 
                 for (space_index, empty_space) in empty_spaces
@@ -85,59 +91,59 @@ impl Packer for RowTightPacker {
                 }
             };
 
-            let atlas_region = Rectangle::new(
-                empty_space.x,
-                empty_space.y,
-                source.region.width,
-                source.region.height,
-            );
+            let footprint = Rectangle::new(empty_space.x, empty_space.y, size.width, size.height);
 
             // choose the best split, horizontal or vertical, to maximize sub areas
-            if empty_space.bottom() - atlas_region.bottom() <= source.region.height {
+            if empty_space.bottom() - footprint.bottom() <= size.height {
                 // vertical slice
                 // * right empty area will be maximized
 
-                if empty_space.width > source.region.width {
+                if empty_space.width > size.width {
                     empty_spaces.push(Rectangle::new(
-                        atlas_region.right() + 1,
-                        atlas_region.top(),
-                        empty_space.width - source.region.width,
+                        footprint.right() + 1,
+                        footprint.top(),
+                        empty_space.width - size.width,
                         empty_space.height,
                     ));
                 }
 
-                if empty_space.height > source.region.height {
+                if empty_space.height > size.height {
                     empty_spaces.push(Rectangle::new(
-                        atlas_region.left(),
-                        atlas_region.bottom() + 1,
-                        source.region.width,
-                        empty_space.height - source.region.height,
+                        footprint.left(),
+                        footprint.bottom() + 1,
+                        size.width,
+                        empty_space.height - size.height,
                     ));
                 }
             } else {
                 // horizontal slice
                 // * bottom empty area will be maximized
 
-                if empty_space.width > source.region.width {
+                if empty_space.width > size.width {
                     empty_spaces.push(Rectangle::new(
-                        atlas_region.right() + 1,
-                        atlas_region.top(),
-                        empty_space.width - source.region.width,
-                        source.region.height,
+                        footprint.right() + 1,
+                        footprint.top(),
+                        empty_space.width - size.width,
+                        size.height,
                     ));
                 }
 
-                if empty_space.height > source.region.height {
+                if empty_space.height > size.height {
                     empty_spaces.push(Rectangle::new(
-                        atlas_region.left(),
-                        atlas_region.bottom() + 1,
+                        footprint.left(),
+                        footprint.bottom() + 1,
                         empty_space.width,
-                        empty_space.height - source.region.height,
+                        empty_space.height - size.height,
                     ));
                 }
             }
 
-            source.atlas_region = Some(atlas_region);
+            source.atlas_region = Some(Rectangle::new(
+                footprint.x,
+                footprint.y,
+                source.region.width,
+                source.region.height,
+            ));
         }
 
         let sum_empty_area: u32 = empty_spaces
@@ -155,3 +161,170 @@ struct SpaceFit {
     pub x: u32,
     pub y: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use image::{GenericImage, Rgba, RgbaImage};
+
+    use super::*;
+    use crate::{graphics::filters, settings::PackerConfig};
+
+    fn source(width: u32, height: u32) -> GraphicSource {
+        GraphicSource {
+            atlas_region: None,
+            buffer: RgbaImage::new(width, height),
+            region: Rectangle::new(0, 0, width, height),
+            pivot: None,
+            hitboxes: BTreeMap::new(),
+        }
+    }
+
+    fn pack_sources(atlas_size: Size<u32>, alignment: u32, sources: &mut [GraphicSource]) {
+        RowTightPacker::new()
+            .execute(
+                atlas_size,
+                &PackingConstraints::new(alignment),
+                &mut sources.iter_mut().collect(),
+            )
+            .unwrap();
+    }
+
+    fn pack(sizes: &[(u32, u32)]) -> Vec<(u32, u32)> {
+        let mut sources: Vec<GraphicSource> = sizes.iter().map(|(w, h)| source(*w, *h)).collect();
+        pack_sources(Size::new(64, 64), 1, &mut sources);
+
+        sources
+            .into_iter()
+            .map(|s| s.atlas_region.map(|r| (r.x, r.y)).unwrap())
+            .collect()
+    }
+
+    fn filled_source(width: u32, height: u32, color: [u8; 4]) -> GraphicSource {
+        let mut source = source(width, height);
+        source.buffer = RgbaImage::from_pixel(width, height, Rgba(color));
+        source
+    }
+
+    #[test]
+    fn tied_sources_are_placed_by_reverse_input_order() {
+        let regions = pack(&[(8, 8), (16, 4), (8, 8), (4, 16), (8, 8)]);
+
+        // last of tied sources takes the top-left most place
+        assert_eq!(regions, vec![(12, 0), (20, 0), (4, 8), (0, 0), (4, 0)]);
+    }
+
+    #[test]
+    fn footprint_and_usable_size_are_aligned() {
+        let constraints = PackingConstraints::new(4);
+        let dimensions = |size: Size<u32>| (size.width, size.height);
+
+        assert_eq!(dimensions(constraints.footprint(&Size::new(5, 3))), (8, 4));
+        assert_eq!(dimensions(constraints.footprint(&Size::new(8, 4))), (8, 4));
+        assert_eq!(
+            dimensions(constraints.usable_size(&Size::new(30, 18))),
+            (28, 16)
+        );
+        assert_eq!(PackingConstraints::new(0).alignment, 1);
+    }
+
+    #[test]
+    fn regions_are_aligned_and_inside_usable_size() {
+        let sizes = [(5, 3), (6, 6), (3, 7), (1, 1), (9, 2)];
+        let mut sources: Vec<GraphicSource> = sizes.iter().map(|(w, h)| source(*w, *h)).collect();
+        pack_sources(Size::new(30, 18), 4, &mut sources);
+
+        let footprints: Vec<Rectangle<u32>> = sources
+            .iter()
+            .map(|s| {
+                let region = s.atlas_region.as_ref().unwrap();
+                let size = PackingConstraints::new(4).footprint(&s.region.size());
+
+                assert_eq!(region.x % 4, 0);
+                assert_eq!(region.y % 4, 0);
+                assert!(region.x + size.width <= 28 && region.y + size.height <= 16);
+
+                Rectangle::new(region.x, region.y, size.width, size.height)
+            })
+            .collect();
+
+        // footprints never overlaps
+        for (i, a) in footprints.iter().enumerate() {
+            for b in &footprints[i + 1..] {
+                assert!(
+                    a.right() < b.left()
+                        || b.right() < a.left()
+                        || a.bottom() < b.top()
+                        || b.bottom() < a.top()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mipmap_levels_follows_alignment() {
+        let levels = |alignment, mipmaps| {
+            PackerConfig {
+                alignment,
+                mipmaps,
+                ..PackerConfig::default()
+            }
+            .mipmap_levels()
+        };
+
+        assert_eq!(levels(4, true), 2);
+        assert_eq!(levels(12, true), 2);
+        assert_eq!(levels(1, true), 0);
+        assert_eq!(levels(0, true), 0);
+        assert_eq!(levels(8, false), 0);
+    }
+
+    #[test]
+    fn last_mipmap_level_keeps_neighbors_apart() {
+        let colors = [[255, 0, 0, 255], [0, 0, 255, 255], [0, 255, 0, 255]];
+        let mut sources = vec![
+            filled_source(5, 3, colors[0]),
+            filled_source(6, 6, colors[1]),
+            filled_source(3, 7, colors[2]),
+        ];
+        pack_sources(Size::new(16, 16), 4, &mut sources);
+
+        let mut atlas = RgbaImage::new(16, 16);
+
+        for source in &sources {
+            let region = source.atlas_region.as_ref().unwrap();
+            atlas.copy_from(&source.buffer, region.x, region.y).unwrap();
+        }
+
+        let config = PackerConfig {
+            alignment: 4,
+            mipmaps: true,
+            ..PackerConfig::default()
+        };
+
+        let mut level = atlas;
+
+        for _ in 0..config.mipmap_levels() {
+            level = filters::downsample(&level);
+        }
+
+        // every pixel at last level comes from a single aligned block,
+        // so it holds only it's source color, with padding lowering alpha
+        assert_eq!(level.dimensions(), (4, 4));
+
+        for (source, color) in sources.iter().zip(colors) {
+            let region = source.atlas_region.as_ref().unwrap();
+            let footprint = PackingConstraints::new(4).footprint(&source.region.size());
+
+            for y in region.y / 4..(region.y + footprint.height) / 4 {
+                for x in region.x / 4..(region.x + footprint.width) / 4 {
+                    let pixel = level.get_pixel(x, y);
+
+                    assert_eq!(pixel.0[..3], color[..3]);
+                    assert!(pixel[3] > 0);
+                }
+            }
+        }
+    }
+}
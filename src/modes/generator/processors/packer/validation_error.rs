@@ -13,6 +13,7 @@ pub enum ValidationError {
     AtlasImageIoError(io::Error),
     CacheNotUpdated,
     PreviousFileImageSizeMismatch,
+    OutputSettingsMismatch,
 }
 
 impl error::Error for ValidationError {
@@ -23,6 +24,7 @@ impl error::Error for ValidationError {
             ValidationError::AtlasImageIoError(io_error) => Some(io_error),
             ValidationError::CacheNotUpdated => None,
             ValidationError::PreviousFileImageSizeMismatch => None,
+            ValidationError::OutputSettingsMismatch => None,
         }
     }
 }
@@ -42,6 +44,10 @@ impl Display for ValidationError {
                 f,
                 "Previous output file image size differs from current size"
             ),
+            ValidationError::OutputSettingsMismatch => write!(
+                f,
+                "Previous output file was generated with different output settings"
+            ),
         }
     }
 }
@@ -10,4 +10,10 @@ pub trait Processor: Verbosity {
     fn retrieve_processor_config<'a>(&self, config: &'a Config) -> Option<&'a dyn ProcessorConfig>;
     fn setup(&mut self, state: &mut State) -> ConfigStatus;
     fn execute(&mut self, state: &mut State);
+
+    /// Hash of every config value which processor output depends on,
+    /// it's cached output is outdated when it changes.
+    fn config_fingerprint(&self, _config: &Config) -> Option<u64> {
+        None
+    }
 }
@@ -1,4 +1,4 @@
-use std::vec::Vec;
+use std::{collections::BTreeMap, vec::Vec};
 
 use crate::{modes::generator::GeneratorModeArgs, settings::Config};
 
@@ -43,6 +43,22 @@ impl<'a> ProcessorsPipeline<'a> {
             }
         }
 
+        // config is fully set up at this point
+        let fingerprints = {
+            let config = state.config.try_read().expect("Can't retrieve a read lock");
+
+            self.processors
+                .iter()
+                .filter_map(|processor| {
+                    processor
+                        .config_fingerprint(&config)
+                        .map(|fingerprint| (processor.name().to_owned(), fingerprint))
+                })
+                .collect::<BTreeMap<String, u64>>()
+        };
+
+        state.fingerprints = fingerprints;
+
         if let ConfigStatus::Modified = config_status {
             // config was modified, we need to save it to keep updated
             let config = state.config.try_read().expect("Can't retrieve a read lock");
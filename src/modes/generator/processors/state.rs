@@ -1,21 +1,33 @@
 use super::{
     cache::{Cache, CacheMetadata, DataOutputMetadata, GenerationMetadata, ImageOutputMetadata},
+    data::DataProcessor,
     image::GraphicOutput,
     output::Output,
 };
 
 use std::{
-    path::PathBuf,
+    collections::BTreeMap,
+    fs::DirEntry,
+    hash::Hasher,
+    path::{Path, PathBuf},
     sync::{Arc, RwLock},
 };
 
-use crate::{modes::generator::GeneratorModeArgs, settings::Config};
+use crate::{
+    modes::generator::GeneratorModeArgs,
+    settings::Config,
+    util::{self, hash::Fnv1a64},
+};
 
 pub struct State<'a> {
     pub config: Arc<RwLock<Config>>,
     pub cache: Option<Cache>,
     pub graphic_output: GraphicOutput,
     pub output: Output<'a>,
+
+    /// Config fingerprint by processor name.
+    pub fingerprints: BTreeMap<String, u64>,
+
     args: &'a GeneratorModeArgs,
 }
 
@@ -28,6 +40,7 @@ impl<'a> State<'a> {
             cache: None,
             graphic_output: GraphicOutput::new(),
             output,
+            fingerprints: BTreeMap::new(),
             args,
         }
     }
@@ -49,12 +62,48 @@ impl<'a> State<'a> {
         CacheMetadata::new(GenerationMetadata {
             image: ImageOutputMetadata {
                 source_directory_modtime,
+                source_files_hash: Self::source_files_hash(&source_directory_path),
                 width: self.output.atlas_width,
                 height: self.output.atlas_height,
+                premultiplied_alpha: c.output.premultiply_alpha,
+                alpha_bleed: c.output.alpha_bleed,
+                alignment: c.packer.alignment,
+                formats: c.output.image_formats.clone(),
+                compression: c.output.compression,
+                png_optimization: c.output.png_optimization,
             },
             data: DataOutputMetadata {
                 prettified: c.data.prettify,
+                companions: c.image.companions.clone(),
+                graphic_key: DataProcessor::resolved_graphic_key(&c.data),
+                default_pivot: c.image.default_pivot,
+                format_version: Some(DataProcessor::resolved_format_version(&c.data)),
             },
+            fingerprints: self.fingerprints.clone(),
         })
     }
+
+    /// Hash of every file path, relative to source directory and at any depth.
+    /// Directory modtime only changes when it's direct entries changes, so it
+    /// catches files added, removed or renamed at sub directories.
+    fn source_files_hash(source_directory_path: &Path) -> u64 {
+        let mut paths = Vec::new();
+
+        util::fs::for_every_file(source_directory_path, &mut |entry: &DirEntry| {
+            if let Ok(path) = entry.path().strip_prefix(source_directory_path) {
+                paths.push(path.to_owned());
+            }
+        })
+        .unwrap();
+
+        paths.sort();
+
+        let mut hasher = Fnv1a64::new();
+        for path in paths {
+            hasher.write(path.to_string_lossy().as_bytes());
+            hasher.write_u8(0);
+        }
+
+        hasher.finish()
+    }
 }
@@ -1,5 +1,6 @@
 pub mod cache;
 pub mod generator;
+pub mod schema;
 
 use crate::{
     args::{Args, ArgsHandler},
@@ -10,6 +11,7 @@ use crate::{
 use cache::CacheMode;
 use colored::Colorize;
 use generator::{GeneratorMode, GeneratorModeArgs};
+use schema::SchemaMode;
 
 trait Mode: ArgsHandler {
     fn run(config: Config, args: &Self::ModeArgs);
@@ -48,5 +50,6 @@ macro_rules! register_modes {
 
 register_modes! {
     CacheMode,
-    GeneratorMode
+    GeneratorMode,
+    SchemaMode
 }
@@ -0,0 +1,228 @@
+use super::Mode;
+use crate::{args::ArgsHandler, settings::Config, GlobalArgs};
+use clap::{Arg, ArgMatches, Command};
+use colored::Colorize;
+use std::{fs, path::PathBuf};
+
+const ATLAS_DATA_SCHEMA: &str = include_str!("../../docs/schemas/atlas-data.schema.json");
+const CONFIG_SCHEMA: &str = include_str!("../../docs/schemas/config.schema.json");
+
+/// Which file format a schema describes.
+#[derive(Copy, Clone)]
+pub enum SchemaKind {
+    Data,
+    Config,
+}
+
+impl SchemaKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchemaKind::Data => "data",
+            SchemaKind::Config => "config",
+        }
+    }
+
+    pub fn filename(&self) -> &'static str {
+        match self {
+            SchemaKind::Data => "atlas-data.schema.json",
+            SchemaKind::Config => "config.schema.json",
+        }
+    }
+
+    pub fn contents(&self) -> &'static str {
+        match self {
+            SchemaKind::Data => ATLAS_DATA_SCHEMA,
+            SchemaKind::Config => CONFIG_SCHEMA,
+        }
+    }
+}
+
+pub struct SchemaModeArgs {
+    pub kind: SchemaKind,
+    pub output: PathBuf,
+}
+
+pub(super) struct SchemaMode;
+
+impl ArgsHandler for SchemaMode {
+    type ModeArgs = SchemaModeArgs;
+
+    fn name() -> &'static str {
+        "schema"
+    }
+
+    fn subcommand<'a>() -> Option<Command<'a>> {
+        Some(
+            Command::new(Self::name())
+                .about("Write JSON Schema of atlas data or config file")
+                .arg(
+                    Arg::new("kind")
+                        .possible_values([SchemaKind::Data.name(), SchemaKind::Config.name()])
+                        .required(true)
+                        .help("Which file format to describe"),
+                )
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .help(
+                            "Where to write schema, defaults to it's filename at current directory",
+                        ),
+                ),
+        )
+    }
+
+    fn handle(matches: &ArgMatches, _global_args: GlobalArgs) -> Self::ModeArgs {
+        let kind = match matches.value_of("kind") {
+            Some("config") => SchemaKind::Config,
+            _ => SchemaKind::Data,
+        };
+
+        SchemaModeArgs {
+            kind,
+            output: matches
+                .value_of("output")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(kind.filename())),
+        }
+    }
+}
+
+impl Mode for SchemaMode {
+    fn run(_config: Config, args: &Self::ModeArgs) {
+        infoln!(block, "Writing {} schema", args.kind.name().bold());
+
+        match fs::write(&args.output, args.kind.contents()) {
+            Ok(()) => infoln!(last, "At {}", args.output.display().to_string().bold()),
+            Err(e) => {
+                errorln!("{}", e);
+                infoln!(last, "{}", "Fail".red());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{
+        graphics::animation::{FrameIndicesGroup, Track},
+        math::{Point, Rectangle, Size},
+        modes::generator::processors::data::{
+            AtlasData, FrameData, GraphicData, PivotData, TextureData,
+        },
+        settings::{ImageFormat, TextureCompression},
+    };
+
+    /// Every object key path, at value, which isn't described by schema.
+    fn undescribed(root: &Value, schema: &Value, value: &Value, path: &str) -> Vec<String> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let schema = root
+                .pointer(reference.trim_start_matches('#'))
+                .unwrap_or_else(|| panic!("Reference '{}' not found", reference));
+
+            return undescribed(root, schema, value, path);
+        }
+
+        // the alternative which describes value best
+        if let Some(alternatives) = schema["oneOf"]
+            .as_array()
+            .or_else(|| schema["anyOf"].as_array())
+        {
+            return alternatives
+                .iter()
+                .map(|alternative| undescribed(root, alternative, value, path))
+                .min_by_key(|keys| keys.len())
+                .unwrap_or_default();
+        }
+
+        match value {
+            Value::Object(entries) => entries
+                .iter()
+                .flat_map(|(key, entry)| {
+                    let entry_path = format!("{}.{}", path, key);
+
+                    match schema["properties"]
+                        .get(key)
+                        .or_else(|| schema.get("additionalProperties"))
+                    {
+                        Some(entry_schema) if entry_schema.is_object() => {
+                            undescribed(root, entry_schema, entry, &entry_path)
+                        }
+                        _ => vec![entry_path],
+                    }
+                })
+                .collect(),
+            Value::Array(items) => items
+                .iter()
+                .flat_map(|item| undescribed(root, &schema["items"], item, path))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn config_schema_describes_every_value() {
+        let schema: Value = serde_json::from_str(CONFIG_SCHEMA).unwrap();
+        let config = serde_json::to_value(Config::default()).unwrap();
+
+        assert_eq!(
+            undescribed(&schema, &schema, &config, ""),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn data_schema_describes_every_value() {
+        let schema: Value = serde_json::from_str(ATLAS_DATA_SCHEMA).unwrap();
+
+        let mut graphic = GraphicData::new();
+        graphic.canvas = Some(Size::new(16, 16));
+        graphic.frames.push(FrameData::Empty);
+        graphic.frames.push(FrameData::Contents {
+            atlas_region: Rectangle::new(0, 0, 8, 8),
+            duration: Some(100),
+            source_region: Rectangle::new(4, 4, 8, 8),
+            pivot: Some(PivotData::new(
+                Point::new(8, 16),
+                &Rectangle::new(4, 4, 8, 8),
+            )),
+            hitboxes: [("hurtbox".to_owned(), Rectangle::new(4, 4, 4, 4))].into(),
+        });
+
+        let mut track = Track::new(Some("walk".to_owned()), FrameIndicesGroup::with(0));
+        track
+            .tracks
+            .register(Track::new(None, FrameIndicesGroup::with_range(0, 1)));
+        graphic.tracks.register(track);
+
+        let mut atlas_data = AtlasData::new();
+        atlas_data
+            .graphics
+            .insert("characters/hero".to_owned(), graphic);
+        atlas_data.meta.format = Some(ImageFormat::Png);
+        atlas_data.meta.hash = Some(format!("{:016x}", 0));
+        atlas_data.meta.textures.push(TextureData {
+            image: "atlas_n.ktx2".to_owned(),
+            format: ImageFormat::Ktx2,
+            compression: Some(TextureCompression::Bc7),
+            companion: Some("_n".to_owned()),
+        });
+
+        let data = serde_json::to_value(&atlas_data).unwrap();
+        assert_eq!(
+            undescribed(&schema, &schema, &data, ""),
+            Vec::<String>::new()
+        );
+
+        let nested = atlas_data.nested().unwrap();
+        assert_eq!(
+            undescribed(&schema, &schema, &nested, ""),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     common::Verbosity,
     log::Logger,
-    settings::{ConfigLoggerStatus, ProcessorConfig},
+    settings::{CacheValidation, ConfigLoggerStatus, ProcessorConfig},
 };
 
 const IMAGES_DIR_NAME: &str = "images";
@@ -21,6 +21,9 @@ pub struct CacheConfig {
 
     #[serde(default)]
     pub identifier: String,
+
+    #[serde(default)]
+    pub validation: CacheValidation,
 }
 
 impl ProcessorConfig for CacheConfig {
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// How cache entries are verified against their source files.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheValidation {
+    /// Source file modified time, fast but any touch (e.g a git checkout) invalidates it.
+    #[default]
+    Modtime,
+
+    /// Source file contents hash, every source file is read at each run.
+    Content,
+}
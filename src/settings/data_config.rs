@@ -3,16 +3,48 @@ use serde::{Deserialize, Serialize};
 use crate::{
     common::Verbosity,
     log::Logger,
-    settings::{ConfigLoggerStatus, ProcessorConfig},
+    settings::{ConfigLoggerStatus, GraphicKey, ProcessorConfig},
 };
 
-#[derive(Default, Serialize, Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct DataConfig {
     #[serde(default)]
     pub verbose: bool,
 
     #[serde(default)]
     pub prettify: bool,
+
+    #[serde(default)]
+    pub graphic_key: GraphicKey,
+
+    /// Data format version to emit, 0 means latest.
+    #[serde(default)]
+    pub format_version: u32,
+
+    #[serde(default = "DataConfig::default_formats")]
+    pub formats: Vec<String>,
+
+    #[serde(default)]
+    pub templates: Vec<String>,
+}
+
+impl DataConfig {
+    pub fn default_formats() -> Vec<String> {
+        vec!["clymene-json".to_owned()]
+    }
+}
+
+impl Default for DataConfig {
+    fn default() -> Self {
+        Self {
+            verbose: false,
+            prettify: false,
+            graphic_key: GraphicKey::default(),
+            format_version: 0,
+            formats: DataConfig::default_formats(),
+            templates: Vec::new(),
+        }
+    }
 }
 
 impl ProcessorConfig for DataConfig {
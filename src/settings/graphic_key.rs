@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// How graphics are keyed at atlas data.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphicKey {
+    /// Source filename only, e.g `hero`.
+    Name,
+
+    /// Source path relative to input directory, e.g `characters/hero`.
+    #[default]
+    Path,
+
+    /// Same as path, but graphics are nested into objects following directories,
+    /// at formats which supports it.
+    Nested,
+}
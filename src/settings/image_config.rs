@@ -1,11 +1,15 @@
-use std::path::PathBuf;
+use std::{
+    hash::Hasher,
+    path::{Path, PathBuf},
+};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
     common::Verbosity,
     log::Logger,
-    settings::{AsepriteConfig, ConfigLoggerStatus, ProcessorConfig},
+    settings::{AsepriteConfig, ConfigLoggerStatus, ImageScale, Pivot, ProcessorConfig},
+    util::hash::Fnv1a64,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -25,6 +29,16 @@ pub struct ImageConfig {
     #[serde(default = "ImageConfig::default_display")]
     pub display: DisplayKind,
 
+    #[serde(default)]
+    pub scale: ImageScale,
+
+    #[serde(default)]
+    pub companions: Vec<String>,
+
+    /// Pivot used by graphics which doesn't provide one.
+    #[serde(default)]
+    pub default_pivot: Option<Pivot>,
+
     #[serde(default)]
     pub aseprite: AsepriteConfig,
 }
@@ -33,6 +47,34 @@ impl ImageConfig {
     pub fn default_display() -> DisplayKind {
         DisplayKind::Simple
     }
+
+    /// Hash of settings which changes how a source file is processed,
+    /// cache entries processed with another one are outdated.
+    pub fn processing_hash(&self) -> u64 {
+        let mut hasher = Fnv1a64::new();
+        hasher.write(self.aseprite.bin_path.as_bytes());
+        hasher.finish()
+    }
+
+    /// Splits a companion source path (without extension) into it's base path and companion suffix.
+    /// E.g. `hero_n` yields (`hero`, `_n`), when `_n` is a registered companion suffix.
+    ///
+    /// It doesn't verify if base really exists.
+    pub fn companion_base(&self, path: &Path) -> Option<(PathBuf, &str)> {
+        let stem = path.file_name()?.to_str()?;
+
+        self.companions
+            .iter()
+            .filter(|suffix| !suffix.is_empty() && stem.len() > suffix.len())
+            .filter(|suffix| stem.ends_with(suffix.as_str()))
+            .max_by_key(|suffix| suffix.len())
+            .map(|suffix| {
+                (
+                    path.with_file_name(&stem[..stem.len() - suffix.len()]),
+                    suffix.as_str(),
+                )
+            })
+    }
 }
 
 impl Default for ImageConfig {
@@ -43,6 +85,9 @@ impl Default for ImageConfig {
             input_path: String::from("source"),
             output_path: PathBuf::default(),
             display: ImageConfig::default_display(),
+            scale: ImageScale::default(),
+            companions: Vec::new(),
+            default_pivot: None,
             aseprite: AsepriteConfig::default(),
         }
     }
@@ -0,0 +1,67 @@
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// File format which an atlas image can be written.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    Png,
+    Webp,
+    Qoi,
+    Tga,
+    Dds,
+    Ktx2,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Webp => "webp",
+            ImageFormat::Qoi => "qoi",
+            ImageFormat::Tga => "tga",
+            ImageFormat::Dds => "dds",
+            ImageFormat::Ktx2 => "ktx2",
+        }
+    }
+
+    /// Checks if it stores gpu block compressed data.
+    pub fn is_compressed(&self) -> bool {
+        self.lossless_format().is_none()
+    }
+
+    /// Lossless format, at `image` crate, used to write uncompressed image formats.
+    pub fn lossless_format(&self) -> Option<image::ImageFormat> {
+        match self {
+            ImageFormat::Png => Some(image::ImageFormat::Png),
+            ImageFormat::Webp => Some(image::ImageFormat::WebP),
+            ImageFormat::Qoi => Some(image::ImageFormat::Qoi),
+            ImageFormat::Tga => Some(image::ImageFormat::Tga),
+            ImageFormat::Dds | ImageFormat::Ktx2 => None,
+        }
+    }
+
+    /// Deserializes a single format or a list of them.
+    pub fn deserialize_one_or_many<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<ImageFormat>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OneOrMany {
+            One(ImageFormat),
+            Many(Vec<ImageFormat>),
+        }
+
+        Ok(match OneOrMany::deserialize(deserializer)? {
+            OneOrMany::One(format) => vec![format],
+            OneOrMany::Many(formats) => formats,
+        })
+    }
+}
+
+impl Display for ImageFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
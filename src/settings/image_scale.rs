@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Integer scale factors applied to every graphic source.
+///
+/// It can be a single value (`scale = 2`) or a list of them (`scale = [1, 2, 3]`),
+/// where every factor yields it's own atlas.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ImageScale {
+    Single(u32),
+    Multiple(Vec<u32>),
+}
+
+impl ImageScale {
+    /// Valid scale factors, sorted and without duplicates.
+    pub fn factors(&self) -> Vec<u32> {
+        let mut factors = match self {
+            ImageScale::Single(factor) => vec![*factor],
+            ImageScale::Multiple(factors) => factors.clone(),
+        };
+
+        factors.retain(|factor| *factor > 0);
+        factors.sort_unstable();
+        factors.dedup();
+
+        if factors.is_empty() {
+            factors.push(1);
+        }
+
+        factors
+    }
+}
+
+impl Default for ImageScale {
+    fn default() -> Self {
+        ImageScale::Single(1)
+    }
+}
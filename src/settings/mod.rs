@@ -1,25 +1,37 @@
 mod aseprite_config;
 mod cache_config;
+mod cache_validation;
 mod config;
 mod config_logger_status;
 mod data_config;
+mod graphic_key;
 mod image_config;
+mod image_format;
+mod image_scale;
 mod load_error;
 mod output_config;
 mod packer_config;
 mod packer_retry_config;
+mod pivot;
 mod processor_config;
 mod save_error;
+mod texture_compression;
 
 pub use aseprite_config::AsepriteConfig;
 pub use cache_config::CacheConfig;
+pub use cache_validation::CacheValidation;
 pub use config::Config;
 pub use config_logger_status::ConfigLoggerStatus;
 pub use data_config::DataConfig;
+pub use graphic_key::GraphicKey;
 pub use image_config::{DisplayKind, ImageConfig};
+pub use image_format::ImageFormat;
+pub use image_scale::ImageScale;
 pub use load_error::LoadError;
 pub use output_config::OutputConfig;
 pub use packer_config::PackerConfig;
 pub use packer_retry_config::PackerRetryConfig;
+pub use pivot::Pivot;
 pub use processor_config::ProcessorConfig;
 pub use save_error::SaveError;
+pub use texture_compression::TextureCompression;
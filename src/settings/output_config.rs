@@ -1,7 +1,7 @@
 use crate::{
     common::Verbosity,
     log::Logger,
-    settings::{ConfigLoggerStatus, ProcessorConfig},
+    settings::{ConfigLoggerStatus, ImageFormat, ProcessorConfig, TextureCompression},
 };
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +18,25 @@ pub struct OutputConfig {
 
     #[serde(default = "OutputConfig::default_path")]
     pub path: String,
+
+    #[serde(default)]
+    pub premultiply_alpha: bool,
+
+    #[serde(default)]
+    pub alpha_bleed: bool,
+
+    #[serde(
+        default = "OutputConfig::default_image_formats",
+        alias = "image_format",
+        deserialize_with = "ImageFormat::deserialize_one_or_many"
+    )]
+    pub image_formats: Vec<ImageFormat>,
+
+    #[serde(default)]
+    pub compression: TextureCompression,
+
+    #[serde(default)]
+    pub png_optimization: u8,
 }
 
 impl OutputConfig {
@@ -29,6 +48,10 @@ impl OutputConfig {
         DEFAULT_FOLDER_PATH.to_string()
     }
 
+    pub fn default_image_formats() -> Vec<ImageFormat> {
+        vec![ImageFormat::Png]
+    }
+
     pub fn name_or_default(&self) -> &str {
         if self.name.is_empty() {
             DEFAULT_NAME
@@ -36,6 +59,22 @@ impl OutputConfig {
             &self.name
         }
     }
+
+    /// Output filename, without extension, to a scale factor.
+    /// Base scale keeps the plain name and any other is suffixed, e.g `atlas@2x`.
+    pub fn scaled_name(&self, scale: u32) -> String {
+        self.page_name("", scale)
+    }
+
+    /// Atlas page filename, without extension, to a companion suffix and scale factor.
+    /// E.g. `atlas_n@2x` to companion `_n` at scale 2.
+    pub fn page_name(&self, companion: &str, scale: u32) -> String {
+        if scale == 1 {
+            format!("{}{}", self.name_or_default(), companion)
+        } else {
+            format!("{}{}@{}x", self.name_or_default(), companion, scale)
+        }
+    }
 }
 
 impl Default for OutputConfig {
@@ -44,6 +83,11 @@ impl Default for OutputConfig {
             verbose: false,
             name: OutputConfig::default_name(),
             path: OutputConfig::default_path(),
+            premultiply_alpha: false,
+            alpha_bleed: false,
+            image_formats: OutputConfig::default_image_formats(),
+            compression: TextureCompression::default(),
+            png_optimization: 0,
         }
     }
 }
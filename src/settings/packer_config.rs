@@ -20,10 +20,34 @@ pub struct PackerConfig {
     #[serde(default)]
     pub force: bool,
 
+    #[serde(default = "PackerConfig::default_alignment")]
+    pub alignment: u32,
+
+    #[serde(default)]
+    pub mipmaps: bool,
+
     #[serde(default)]
     pub retry: PackerRetryConfig,
 }
 
+impl PackerConfig {
+    pub fn default_alignment() -> u32 {
+        1
+    }
+
+    /// Mipmap levels, besides base level, which are safe from bleeding between atlas regions.
+    ///
+    /// Every region is aligned to `alignment`, so it's largest power of 2 factor
+    /// defines how many times it can be halved without mixing regions pixels.
+    pub fn mipmap_levels(&self) -> u32 {
+        if self.mipmaps && self.alignment > 0 {
+            self.alignment.trailing_zeros()
+        } else {
+            0
+        }
+    }
+}
+
 impl Default for PackerConfig {
     fn default() -> Self {
         Self {
@@ -31,6 +55,8 @@ impl Default for PackerConfig {
             atlas_size: 1024,
             optimize: true,
             force: false,
+            alignment: PackerConfig::default_alignment(),
+            mipmaps: false,
             retry: PackerRetryConfig::default(),
         }
     }
@@ -0,0 +1,47 @@
+use serde::{
+    de::{value, IntoDeserializer},
+    Deserialize, Serialize,
+};
+
+use crate::math::{Point, Size};
+
+/// Named pivot point, placed relative to a graphic's source canvas.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Pivot {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl Pivot {
+    /// Pivot by it's name, as used at config, e.g `bottom-center`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::deserialize(IntoDeserializer::<value::Error>::into_deserializer(name)).ok()
+    }
+
+    /// Position at canvas coordinates.
+    pub fn position(&self, canvas: &Size<u32>) -> Point<i32> {
+        let (width, height) = (canvas.width as i32, canvas.height as i32);
+
+        let x = match self {
+            Pivot::TopLeft | Pivot::CenterLeft | Pivot::BottomLeft => 0,
+            Pivot::TopCenter | Pivot::Center | Pivot::BottomCenter => width / 2,
+            Pivot::TopRight | Pivot::CenterRight | Pivot::BottomRight => width,
+        };
+
+        let y = match self {
+            Pivot::TopLeft | Pivot::TopCenter | Pivot::TopRight => 0,
+            Pivot::CenterLeft | Pivot::Center | Pivot::CenterRight => height / 2,
+            Pivot::BottomLeft | Pivot::BottomCenter | Pivot::BottomRight => height,
+        };
+
+        Point::new(x, y)
+    }
+}
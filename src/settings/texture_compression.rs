@@ -0,0 +1,23 @@
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// Gpu block compression used at compressed image formats.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextureCompression {
+    Bc3,
+    #[default]
+    Bc7,
+    Etc2,
+}
+
+impl Display for TextureCompression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TextureCompression::Bc3 => write!(f, "bc3"),
+            TextureCompression::Bc7 => write!(f, "bc7"),
+            TextureCompression::Etc2 => write!(f, "etc2"),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use serde::Serialize;
+use std::{
+    fs::File,
+    hash::Hasher,
+    io::{self, Read},
+    path::Path,
+};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// 64 bits FNV-1a hash.
+pub fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hasher = Fnv1a64::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// 64 bits FNV-1a hash of a value serialized as json,
+/// so it only changes when value does.
+pub fn fingerprint<T: Serialize>(value: &T) -> u64 {
+    fnv1a_64(&serde_json::to_vec(value).expect("Value can't be serialized"))
+}
+
+/// 64 bits FNV-1a hash of a file contents, read in chunks.
+pub fn file_fnv1a_64<P: AsRef<Path>>(path: P) -> io::Result<u64> {
+    let mut file = File::open(path)?;
+    let mut hasher = Fnv1a64::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        match file.read(&mut buffer)? {
+            0 => break,
+            len => hasher.write(&buffer[..len]),
+        }
+    }
+
+    Ok(hasher.finish())
+}
+
+/// Incremental 64 bits FNV-1a hasher.
+pub struct Fnv1a64 {
+    hash: u64,
+}
+
+impl Fnv1a64 {
+    pub fn new() -> Self {
+        Self {
+            hash: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl Default for Fnv1a64 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Fnv1a64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash = bytes.iter().fold(self.hash, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+        });
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
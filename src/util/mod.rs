@@ -1,6 +1,7 @@
 use std::time::{Duration, Instant};
 
 pub mod fs;
+pub mod hash;
 
 pub struct Timer {
     start_instant: Instant,